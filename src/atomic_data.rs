@@ -13,6 +13,7 @@ pub struct Orbital {
     pub n: u32,
     pub l: u32,
     pub label: String,
+    pub occupation: Option<f32>,
     pub radial_r: Vec<f32>,
     pub radial_chi: Vec<f32>,
 }
@@ -22,6 +23,7 @@ pub struct ElementData {
     pub symbol: String,
     pub orbitals: Vec<Orbital>,
     pub r_max: f32,
+    pub data_file: String,
 }
 
 static ELEMENT_CACHE: Lazy<RwLock<HashMap<String, ElementData>>> =
@@ -147,6 +149,7 @@ fn parse_upf(path: &Path, symbol: &str) -> Result<ElementData, String> {
     let mut in_pp_r = false;
     let mut current_label: Option<String> = None;
     let mut current_l: Option<u32> = None;
+    let mut current_occupation: Option<f32> = None;
     let mut current_vals: Vec<f32> = Vec::new();
 
     loop {
@@ -158,6 +161,7 @@ fn parse_upf(path: &Path, symbol: &str) -> Result<ElementData, String> {
                 } else if name.starts_with(b"PP_CHI") {
                     current_label = None;
                     current_l = None;
+                    current_occupation = None;
                     current_vals.clear();
                     for attr in e.attributes().flatten() {
                         if attr.key.as_ref() == b"label" {
@@ -166,6 +170,10 @@ fn parse_upf(path: &Path, symbol: &str) -> Result<ElementData, String> {
                             if let Ok(v) = attr.unescape_value().unwrap_or_default().parse::<u32>() {
                                 current_l = Some(v);
                             }
+                        } else if attr.key.as_ref() == b"occupation" {
+                            if let Ok(v) = attr.unescape_value().unwrap_or_default().trim().parse::<f32>() {
+                                current_occupation = Some(v);
+                            }
                         }
                     }
                 }
@@ -189,6 +197,7 @@ fn parse_upf(path: &Path, symbol: &str) -> Result<ElementData, String> {
                             n,
                             l,
                             label,
+                            occupation: current_occupation.take(),
                             radial_r: radial_r.clone(),
                             radial_chi: current_vals.clone(),
                         });
@@ -212,6 +221,7 @@ fn parse_upf(path: &Path, symbol: &str) -> Result<ElementData, String> {
         symbol: symbol.to_string(),
         orbitals,
         r_max,
+        data_file: path.display().to_string(),
     })
 }
 
@@ -24,6 +24,7 @@ pub struct LdaElement {
     pub total_electrons: f32,
     pub valence_electrons: f32,
     pub r_max: f32,
+    pub data_file: String,
 }
 
 static ELEMENT_CACHE: Lazy<RwLock<HashMap<String, LdaElement>>> =
@@ -120,7 +121,7 @@ async fn download_to(url: &str, path: &Path) -> Result<(), String> {
     fs::write(path, &bytes).map_err(|e| format!("write file: {e}"))
 }
 
-fn parse_alog(path: &Path, symbol: &str) -> Result<LdaElement, String> {
+pub(crate) fn parse_alog(path: &Path, symbol: &str) -> Result<LdaElement, String> {
     let mut file = fs::File::open(path).map_err(|e| format!("open LDA file: {e}"))?;
     let mut content = String::new();
     file.read_to_string(&mut content)
@@ -141,9 +142,18 @@ fn parse_alog(path: &Path, symbol: &str) -> Result<LdaElement, String> {
         total_electrons,
         valence_electrons,
         r_max,
+        data_file: path.display().to_string(),
     })
 }
 
+#[cfg(test)]
+pub(crate) fn cache_element(element: LdaElement) {
+    ELEMENT_CACHE
+        .write()
+        .unwrap()
+        .insert(element.symbol.clone(), element);
+}
+
 fn extract_value(content: &str, key: &str) -> Option<f32> {
     let escaped = regex::escape(key);
     let re = Regex::new(&format!(r"{escaped}\s+([0-9Ee\+\-\.]+)")).ok()?;
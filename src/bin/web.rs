@@ -1,13 +1,17 @@
 use axum::{
     extract::Query,
-    http::header,
-    response::{Html, IntoResponse},
+    http::{header, StatusCode},
+    response::{Html, IntoResponse, Response},
     routing::get,
     Json, Router,
 };
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
 #[path = "../physics.rs"]
 mod physics;
@@ -42,6 +46,10 @@ struct SampleQuery {
     bubble: Option<bool>,
     basis: Option<String>,
     color_mode: Option<String>,
+    mean_radius: Option<bool>,
+    session: Option<String>,
+    provenance: Option<bool>,
+    profile: Option<bool>,
 }
 
 #[derive(Serialize)]
@@ -60,6 +68,7 @@ struct SampleResponse {
     source: String,
     note: Option<String>,
     available_orbitals: Vec<OrbitalInfo>,
+    default_orbital: Option<OrbitalInfo>,
     selected_orbital: Option<String>,
     selected_orbital_b: Option<String>,
     mix: Option<f32>,
@@ -70,6 +79,77 @@ struct SampleResponse {
     signs: Option<Vec<i8>>,
     phases: Option<Vec<f32>>,
     intensities: Option<Vec<f32>>,
+    shell_index: Option<Vec<u8>>,
+    empirical_mean_radius: Option<f32>,
+    length_unit: Option<String>,
+    session: Option<String>,
+    data_file: Option<String>,
+    profile: Option<SamplingProfile>,
+}
+
+impl SampleResponse {
+    fn with_mean_radius(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.empirical_mean_radius = empirical_mean_radius(&self.samples);
+        }
+        self
+    }
+}
+
+// Positions plus the time-independent psi1/psi2 parts.
+type Skeleton = (Vec<[f32; 3]>, Vec<[f32; 2]>, Vec<[f32; 2]>);
+
+struct SkeletonSession {
+    key: String,
+    skeleton: Arc<Skeleton>,
+    touched: Instant,
+}
+
+static SKELETON_SESSIONS: Lazy<RwLock<HashMap<String, SkeletonSession>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+const SKELETON_TTL: Duration = Duration::from_secs(30);
+const MAX_SKELETON_SESSIONS: usize = 16;
+// Total positions held across sessions (about 28 MB with psi1/psi2), so a few
+// 500k-point clouds can't crowd out everyone else indefinitely.
+const MAX_SKELETON_SAMPLES: usize = 1_000_000;
+
+// Timings in milliseconds.
+#[derive(Serialize, Clone, Copy, Default)]
+struct SamplingProfile {
+    radial_cdf_ms: f32,
+    angular_max_ms: f32,
+    rejection_ms: f32,
+    total_ms: f32,
+    attempts: usize,
+    accepted: usize,
+    acceptance_ratio: f32,
+}
+
+impl SamplingProfile {
+    fn record(
+        &mut self,
+        start: Instant,
+        cdf_start: Instant,
+        angular_start: Instant,
+        loop_start: Instant,
+        attempts: usize,
+        accepted: usize,
+    ) {
+        let ms = |d: Duration| d.as_secs_f32() * 1000.0;
+        let end = Instant::now();
+        self.radial_cdf_ms = ms(angular_start.duration_since(cdf_start));
+        self.angular_max_ms = ms(loop_start.duration_since(angular_start));
+        self.rejection_ms = ms(end.duration_since(loop_start));
+        self.total_ms = ms(end.duration_since(start));
+        self.attempts = attempts;
+        self.accepted = accepted;
+        self.acceptance_ratio = if attempts > 0 {
+            accepted as f32 / attempts as f32
+        } else {
+            0.0
+        };
+    }
 }
 
 #[derive(Serialize, Clone)]
@@ -128,6 +208,112 @@ enum RadialKind {
     Chi,
 }
 
+#[derive(Deserialize)]
+struct RadialQuery {
+    n: Option<u32>,
+    l: Option<u32>,
+    z: Option<u32>,
+    max: Option<f32>,
+    points: Option<usize>,
+    radial_form: Option<String>,
+}
+
+#[derive(Serialize)]
+struct RadialResponse {
+    n: u32,
+    l: u32,
+    z: u32,
+    source: String,
+    radial_form: String,
+    note: Option<String>,
+    r: Vec<f32>,
+    values: Vec<f32>,
+}
+
+// R(r), u(r) = r R(r), or P(r) = u(r)^2.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RadialForm {
+    R,
+    U,
+    P,
+}
+
+impl RadialForm {
+    fn from_query(value: Option<&str>) -> Self {
+        match value.unwrap_or("R") {
+            "u" | "U" => RadialForm::U,
+            "p" | "P" => RadialForm::P,
+            _ => RadialForm::R,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            RadialForm::R => "R",
+            RadialForm::U => "u",
+            RadialForm::P => "P",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ImageQuery {
+    n: Option<u32>,
+    l: Option<u32>,
+    m: Option<i32>,
+    z: Option<u32>,
+    max: Option<f32>,
+    width: Option<u32>,
+    height: Option<u32>,
+    basis: Option<String>,
+    colormap: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Colormap {
+    Intensity,
+    Radial,
+    Gray,
+}
+
+impl Colormap {
+    fn from_query(value: Option<&str>) -> Self {
+        match value.unwrap_or("intensity").to_lowercase().as_str() {
+            "radial" => Colormap::Radial,
+            "gray" | "grey" => Colormap::Gray,
+            _ => Colormap::Intensity,
+        }
+    }
+
+    fn color(self, t: f32) -> [u8; 3] {
+        // Same stops as colorForIntensity / colorForDistance in INDEX_HTML.
+        const INTENSITY: [(f32, [f32; 3]); 5] = [
+            (0.0, [0.02, 0.02, 0.08]),
+            (0.25, [0.25, 0.05, 0.45]),
+            (0.55, [0.85, 0.2, 0.2]),
+            (0.8, [0.98, 0.72, 0.2]),
+            (1.0, [1.0, 1.0, 1.0]),
+        ];
+        const RADIAL: [(f32, [f32; 3]); 5] = [
+            (0.0, [0.0, 0.0, 1.0]),
+            (0.25, [0.0, 1.0, 1.0]),
+            (0.5, [0.0, 1.0, 0.0]),
+            (0.75, [1.0, 1.0, 0.0]),
+            (1.0, [1.0, 0.0, 0.0]),
+        ];
+        let t = if t.is_finite() { t.clamp(0.0, 1.0) } else { 0.0 };
+        let rgb = match self {
+            Colormap::Intensity => interp_stops(&INTENSITY, t.powf(0.4)),
+            Colormap::Radial => interp_stops(&RADIAL, t),
+            Colormap::Gray => {
+                let v = t.powf(0.4);
+                [v, v, v]
+            }
+        };
+        rgb.map(|c| (c * 255.0).round().clamp(0.0, 255.0) as u8)
+    }
+}
+
 const INDEX_HTML: &str = r##"<!doctype html>
 <html lang="en">
   <head>
@@ -267,6 +453,7 @@ const INDEX_HTML: &str = r##"<!doctype html>
               <select id="renderMode">
                 <option value="dots" selected>Dots</option>
                 <option value="bubbles">Bubbles</option>
+                <option value="volume">Volume (experimental)</option>
               </select>
             </div>
             <div id="dotColorRow" class="row">
@@ -275,6 +462,7 @@ const INDEX_HTML: &str = r##"<!doctype html>
                 <option value="radial" selected>Radial</option>
                 <option value="phase">Phase</option>
                 <option value="intensity">Intensity</option>
+                <option value="shells">Radial shells</option>
               </select>
             </div>
             <div id="dotSizeRow" class="row">
@@ -282,6 +470,13 @@ const INDEX_HTML: &str = r##"<!doctype html>
               <input id="dotSize" type="range" min="0.0005" max="0.1" step="0.0005" value="0.002" />
               <span id="dotSizeVal">0.002</span>
             </div>
+            <div id="dotStyleRow" class="row">
+              <label>Dot style</label>
+              <select id="dotStyle">
+                <option value="glow" selected>Glow (additive)</option>
+                <option value="spheres">Transparent spheres (sorted)</option>
+              </select>
+            </div>
             <div id="bubbleThresholdRow" class="row" style="display: none;">
               <label>Threshold</label>
               <input id="bubbleThreshold" type="range" min="0.10" max="0.90" step="0.02" value="0.45" />
@@ -354,6 +549,15 @@ const INDEX_HTML: &str = r##"<!doctype html>
               <label>l</label><input id="l" type="number" min="0" value="1" />
               <label>m</label><input id="m" type="number" value="0" />
             </div>
+            <div id="morphRow" class="row" style="display: none;">
+              <label>Morph to</label>
+              <label>n</label><input id="morphN" type="number" min="1" value="3" />
+              <label>l</label><input id="morphL" type="number" min="0" value="2" />
+              <label>m</label><input id="morphM" type="number" value="0" />
+              <input id="morphSeconds" type="number" min="0.5" max="20" step="0.5" value="3" title="Duration (s)" />
+              <button id="morphGo">Morph</button>
+            </div>
+            <div id="morphNote" class="hint" style="display: none;">Illustrative only: points slide in a straight line from one cloud to the other. Real transitions go through superpositions.</div>
             <div id="mixRow" class="row">
               <label>mix</label>
               <input id="mix" type="range" min="0.05" max="0.95" step="0.01" value="0.50" />
@@ -370,6 +574,7 @@ const INDEX_HTML: &str = r##"<!doctype html>
               <label>cnt</label><input id="count" type="number" min="1000" step="1000" value="50000" />
               <label>max</label><input id="max" type="number" min="1" step="1" value="20" />
             </div>
+            <div id="countWarning" class="hint" style="display: none;"></div>
           </div>
         </div>
 
@@ -423,6 +628,7 @@ const INDEX_HTML: &str = r##"<!doctype html>
       import * as THREE from "/static/three.module.js";
       import { MarchingCubes } from "/static/MarchingCubes.js";
 
+      const serverDefaults = __SERVER_DEFAULTS__;
       const statusEl = document.getElementById("status");
       const panel = document.getElementById("panel");
       const panelInner = document.getElementById("panelInner");
@@ -443,6 +649,13 @@ const INDEX_HTML: &str = r##"<!doctype html>
       const superRow = document.getElementById("superRow");
       const superPickRow = document.getElementById("superPickRow");
       const mixRow = document.getElementById("mixRow");
+      const morphRow = document.getElementById("morphRow");
+      const morphNote = document.getElementById("morphNote");
+      const morphNInput = document.getElementById("morphN");
+      const morphLInput = document.getElementById("morphL");
+      const morphMInput = document.getElementById("morphM");
+      const morphSecondsInput = document.getElementById("morphSeconds");
+      const morphGoButton = document.getElementById("morphGo");
       const mixInput = document.getElementById("mix");
       const mixVal = document.getElementById("mixVal");
       const modeSelect = document.getElementById("mode");
@@ -450,6 +663,8 @@ const INDEX_HTML: &str = r##"<!doctype html>
       const dotColorSelect = document.getElementById("dotColorMode");
       const dotColorRow = document.getElementById("dotColorRow");
       const dotSizeRow = document.getElementById("dotSizeRow");
+      const dotStyleRow = document.getElementById("dotStyleRow");
+      const dotStyleSelect = document.getElementById("dotStyle");
       const dotSizeInput = document.getElementById("dotSize");
       const dotSizeVal = document.getElementById("dotSizeVal");
       const valenceRow = document.getElementById("valenceRow");
@@ -463,6 +678,7 @@ const INDEX_HTML: &str = r##"<!doctype html>
       const bubbleQualityInput = document.getElementById("bubbleQuality");
       const bubbleQualityVal = document.getElementById("bubbleQualityVal");
       const countInput = document.getElementById("count");
+      const countWarning = document.getElementById("countWarning");
       const maxInput = document.getElementById("max");
       const nInput = document.getElementById("n");
       const lInput = document.getElementById("l");
@@ -506,6 +722,125 @@ const INDEX_HTML: &str = r##"<!doctype html>
         return tex;
       })();
 
+      // Shaded ball sprite for the sorted, normally blended sphere style.
+      const sphereTexture = (() => {
+        const size = 64;
+        const canvas = document.createElement("canvas");
+        canvas.width = size;
+        canvas.height = size;
+        const ctx = canvas.getContext("2d");
+        ctx.clearRect(0, 0, size, size);
+        const gradient = ctx.createRadialGradient(size * 0.38, size * 0.36, size * 0.04, size / 2, size / 2, size / 2 - 1);
+        gradient.addColorStop(0.0, "#ffffff");
+        gradient.addColorStop(0.6, "#b8b8b8");
+        gradient.addColorStop(1.0, "#4a4a4a");
+        ctx.beginPath();
+        ctx.arc(size / 2, size / 2, size / 2 - 1, 0, Math.PI * 2);
+        ctx.fillStyle = gradient;
+        ctx.fill();
+        const tex = new THREE.CanvasTexture(canvas);
+        tex.generateMipmaps = false;
+        tex.minFilter = THREE.LinearFilter;
+        tex.magFilter = THREE.LinearFilter;
+        return tex;
+      })();
+
+      let dotStyle = localStorage.getItem("dotStyle") || "glow";
+      let sortIndex = null;
+      let sortDepth = null;
+      const sortBucketCount = 4096;
+      const sortBuckets = new Uint32Array(sortBucketCount + 1);
+      let lastSortTime = 0;
+      let lastSortVersion = -1;
+      const lastSortCam = new THREE.Vector3(Infinity, Infinity, Infinity);
+      const tmpSortCam = new THREE.Vector3();
+
+      function makeDotMaterial() {
+        if (dotStyle === "spheres") {
+          // Normal blending is order dependent, so points are drawn through a back-to-front index.
+          return new THREE.PointsMaterial({
+            size: dotSize,
+            vertexColors: true,
+            transparent: true,
+            opacity: 0.55,
+            depthWrite: false,
+            blending: THREE.NormalBlending,
+            map: sphereTexture,
+            alphaTest: 0.05,
+          });
+        }
+        return new THREE.PointsMaterial({
+          size: dotSize,
+          vertexColors: true,
+          transparent: true,
+          opacity: 0.6,
+          depthWrite: false,
+          blending: THREE.AdditiveBlending,
+          map: circleTexture,
+          alphaTest: 0.4,
+        });
+      }
+
+      function sortPointsBackToFront() {
+        if (!points || !posAttr) return;
+        const count = posAttr.count;
+        const geometry = points.geometry;
+        if (!sortIndex || sortIndex.length !== count || !geometry.index || geometry.index.array !== sortIndex) {
+          sortIndex = new Uint32Array(count);
+          sortDepth = new Float32Array(count);
+          for (let i = 0; i < count; i++) {
+            sortIndex[i] = i;
+          }
+          geometry.setIndex(new THREE.BufferAttribute(sortIndex, 1));
+          lastSortVersion = -1;
+        }
+        points.updateMatrixWorld();
+        const cam = points.worldToLocal(tmpSortCam.copy(camera.position));
+        if (posAttr.version === lastSortVersion && cam.distanceToSquared(lastSortCam) < 1e-10) {
+          return;
+        }
+        lastSortVersion = posAttr.version;
+        lastSortCam.copy(cam);
+        // Counting sort on quantized distance: O(count) per pass, so spinning
+        // clouds (positions change every frame) stay cheap at 500k points.
+        const arr = posAttr.array;
+        let minD = Infinity;
+        let maxD = 0;
+        for (let i = 0; i < count; i++) {
+          const dx = arr[i * 3 + 0] - cam.x;
+          const dy = arr[i * 3 + 1] - cam.y;
+          const dz = arr[i * 3 + 2] - cam.z;
+          const d = Math.sqrt(dx * dx + dy * dy + dz * dz);
+          sortDepth[i] = d;
+          if (d < minD) minD = d;
+          if (d > maxD) maxD = d;
+        }
+        const scale = (sortBucketCount - 1) / Math.max(maxD - minD, 1e-9);
+        sortBuckets.fill(0);
+        for (let i = 0; i < count; i++) {
+          // Far points get low buckets so they are drawn first.
+          sortBuckets[sortBucketCount - 1 - Math.floor((sortDepth[i] - minD) * scale) + 1] += 1;
+        }
+        for (let b = 1; b <= sortBucketCount; b++) {
+          sortBuckets[b] += sortBuckets[b - 1];
+        }
+        for (let i = 0; i < count; i++) {
+          const bucket = sortBucketCount - 1 - Math.floor((sortDepth[i] - minD) * scale);
+          sortIndex[sortBuckets[bucket]++] = i;
+        }
+        geometry.index.needsUpdate = true;
+      }
+
+      function applyDotStyle() {
+        if (!points) return;
+        points.material.dispose();
+        points.material = makeDotMaterial();
+        if (dotStyle !== "spheres") {
+          points.geometry.setIndex(null);
+          sortIndex = null;
+        }
+      }
+
       const animToggle = document.getElementById("animated");
       const animSpeedInput = document.getElementById("animSpeed");
       const animSpeedVal = document.getElementById("animSpeedVal");
@@ -523,8 +858,11 @@ const INDEX_HTML: &str = r##"<!doctype html>
       let animTo = null;
       let animStart = 0;
       let animDurationMs = 600;
+      let morphActive = false;
+      let morphTarget = null;
       let lastSampleTime = 0;
       let lastOrbitals = [];
+      let pendingDefaultOrbital = false;
       let renderMode = "dots";
       let bubbleGroup = null;
       let bubblePos = null;
@@ -534,7 +872,14 @@ const INDEX_HTML: &str = r##"<!doctype html>
       let lastExtent = 1.0;
       let lastBubbleUpdate = 0;
       let bubbleDirty = false;
+      let bubbleNoSurface = false;
+      let superSession = null;
+      // Once the user types an m, keep it: m=0 is a valid real orbital (d_z2, f_z3), not a default to bump.
+      let mPinned = false;
+      let m2Pinned = false;
+      let bubbleStatusBackup = "";
       let bubbleSampleTarget = 3500;
+      const dotCountWarnLimit = 150000;
       let bubbleResolution = 48;
       const bubbleKernelRadius = 1;
       let bubbleKernelSigma = 0.45;
@@ -543,6 +888,13 @@ const INDEX_HTML: &str = r##"<!doctype html>
       let bubbleQuality = 2;
       let dotColorMode = "radial";
       let dotSize = 0.002;
+      const volumeSupported = renderer.capabilities.isWebGL2;
+      let volumeMesh = null;
+      let volumeTexture = null;
+      let volumeData = null;
+      let volumeField = null;
+      const volumeResolution = 64;
+      const volumeSampleTarget = 60000;
       let spinTime = 0;
       let spinPhi0 = null;
       let spinRho = null;
@@ -599,6 +951,96 @@ const INDEX_HTML: &str = r##"<!doctype html>
         }
       }
 
+      const VOLUME_VERTEX_SHADER = `
+        out vec3 vOrigin;
+        out vec3 vDirection;
+        void main() {
+          vOrigin = vec3(inverse(modelMatrix) * vec4(cameraPosition, 1.0)).xyz;
+          vDirection = position - vOrigin;
+          gl_Position = projectionMatrix * modelViewMatrix * vec4(position, 1.0);
+        }
+      `;
+
+      const VOLUME_FRAGMENT_SHADER = `
+        precision highp float;
+        precision highp sampler3D;
+        in vec3 vOrigin;
+        in vec3 vDirection;
+        out vec4 fragColor;
+        uniform sampler3D map;
+        uniform vec3 colorPos;
+        uniform vec3 colorNeg;
+        uniform float threshold;
+        uniform float opacity;
+        uniform float steps;
+
+        vec2 hitBox(vec3 orig, vec3 dir) {
+          vec3 invDir = 1.0 / dir;
+          vec3 tA = (vec3(-0.5) - orig) * invDir;
+          vec3 tB = (vec3(0.5) - orig) * invDir;
+          vec3 tMin = min(tA, tB);
+          vec3 tMax = max(tA, tB);
+          return vec2(max(tMin.x, max(tMin.y, tMin.z)), min(tMax.x, min(tMax.y, tMax.z)));
+        }
+
+        void main() {
+          vec3 rayDir = normalize(vDirection);
+          vec2 bounds = hitBox(vOrigin, rayDir);
+          if (bounds.x > bounds.y) discard;
+          bounds.x = max(bounds.x, 0.0);
+          vec3 p = vOrigin + bounds.x * rayDir;
+          vec3 inc = 1.0 / abs(rayDir);
+          float delta = min(inc.x, min(inc.y, inc.z)) / steps;
+          vec4 acc = vec4(0.0);
+          for (float t = bounds.x; t < bounds.y; t += delta) {
+            float s = texture(map, p + 0.5).r * 2.0 - 1.0;
+            float d = abs(s);
+            if (d > threshold) {
+              float a = clamp((d - threshold) * opacity, 0.0, 1.0);
+              vec3 c = (s >= 0.0 ? colorPos : colorNeg) * (0.6 + 0.8 * d);
+              acc.rgb += (1.0 - acc.a) * a * c;
+              acc.a += (1.0 - acc.a) * a;
+              if (acc.a >= 0.97) break;
+            }
+            p += rayDir * delta;
+          }
+          if (acc.a <= 0.0) discard;
+          fragColor = vec4(acc.rgb / acc.a, acc.a);
+        }
+      `;
+
+      function initVolume() {
+        if (volumeMesh) return;
+        const size = volumeResolution;
+        volumeData = new Uint8Array(size * size * size);
+        volumeData.fill(128);
+        volumeTexture = new THREE.Data3DTexture(volumeData, size, size, size);
+        volumeTexture.format = THREE.RedFormat;
+        volumeTexture.minFilter = THREE.LinearFilter;
+        volumeTexture.magFilter = THREE.LinearFilter;
+        volumeTexture.unpackAlignment = 1;
+        volumeTexture.needsUpdate = true;
+        const material = new THREE.ShaderMaterial({
+          glslVersion: THREE.GLSL3,
+          uniforms: {
+            map: { value: volumeTexture },
+            colorPos: { value: new THREE.Color(0xff3b4a) },
+            colorNeg: { value: new THREE.Color(0x3b5bff) },
+            threshold: { value: 0.03 },
+            opacity: { value: 0.12 },
+            steps: { value: 160 },
+          },
+          vertexShader: VOLUME_VERTEX_SHADER,
+          fragmentShader: VOLUME_FRAGMENT_SHADER,
+          side: THREE.BackSide,
+          transparent: true,
+          depthWrite: false,
+        });
+        volumeMesh = new THREE.Mesh(new THREE.BoxGeometry(1, 1, 1), material);
+        volumeMesh.visible = false;
+        scene.add(volumeMesh);
+      }
+
       const bubbleQualityPresets = [
         { label: "Low", resolution: 36, samples: 3000, sigma: 0.5, interval: 80 },
         { label: "Medium", resolution: 48, samples: 5000, sigma: 0.45, interval: 60 },
@@ -620,6 +1062,7 @@ const INDEX_HTML: &str = r##"<!doctype html>
         if (persist) {
           localStorage.setItem("bubbleQuality", String(bubbleQuality));
         }
+        updateCountWarning();
         if (bubbleQuality >= 3 && bubbleIsoFraction > 0.8) {
           bubbleIsoFraction = 0.65;
           bubbleThresholdInput.value = bubbleIsoFraction.toFixed(2);
@@ -642,17 +1085,24 @@ const INDEX_HTML: &str = r##"<!doctype html>
       }
 
       function updateRenderMode() {
+        if (renderModeSelect.value === "volume" && !volumeSupported) {
+          renderModeSelect.value = "dots";
+          statusEl.textContent = "Volume rendering needs WebGL2 3D textures; falling back to dots.";
+        }
         renderMode = renderModeSelect.value;
         localStorage.setItem("renderMode", renderMode);
         const showBubbles = renderMode === "bubbles";
+        const showVolume = renderMode === "volume";
+        const showDots = renderMode === "dots";
         bubbleThresholdRow.style.display = showBubbles ? "flex" : "none";
         bubbleQualityRow.style.display = showBubbles ? "flex" : "none";
-        dotColorRow.style.display = showBubbles ? "none" : "flex";
-        dotSizeRow.style.display = showBubbles ? "none" : "flex";
-        dotColorSelect.disabled = showBubbles;
+        dotColorRow.style.display = showDots ? "flex" : "none";
+        dotSizeRow.style.display = showDots ? "flex" : "none";
+        dotStyleRow.style.display = showDots ? "flex" : "none";
+        dotColorSelect.disabled = !showDots;
         updateModeUI();
         if (points) {
-          points.visible = !showBubbles;
+          points.visible = showDots;
         }
         if (showBubbles) {
           initBubbles();
@@ -663,6 +1113,32 @@ const INDEX_HTML: &str = r##"<!doctype html>
         } else if (bubbleGroup) {
           bubbleGroup.visible = false;
         }
+        if (!showBubbles && bubbleNoSurface) {
+          bubbleNoSurface = false;
+          statusEl.textContent = bubbleStatusBackup;
+        }
+        if (showVolume) {
+          initVolume();
+          volumeMesh.visible = true;
+          if (posAttr) {
+            updateVolumeFromPositions(posAttr.array, lastSigns);
+          }
+        } else if (volumeMesh) {
+          volumeMesh.visible = false;
+        }
+        updateCountWarning();
+      }
+
+      function updateCountWarning() {
+        const count = Number(countInput.value);
+        let text = "";
+        if (renderMode === "bubbles" && count > bubbleSampleTarget) {
+          text = `Bubbles only use ~${bubbleSampleTarget} samples, so that is all that gets fetched.`;
+        } else if (renderMode === "dots" && count > dotCountWarnLimit) {
+          text = `Over ${dotCountWarnLimit / 1000}k dots can stall the browser (large download and draw).`;
+        }
+        countWarning.textContent = text;
+        countWarning.style.display = text ? "block" : "none";
       }
 
       function updateBubblesFromPositions(arr, signs) {
@@ -741,6 +1217,71 @@ const INDEX_HTML: &str = r##"<!doctype html>
         bubblePos.update();
         bubbleNeg.update();
         bubbleDirty = false;
+
+        const hasSurface = (bubblePos.visible && bubblePos.count > 0)
+          || (bubbleNeg.visible && bubbleNeg.count > 0);
+        if (!hasSurface && !bubbleNoSurface) {
+          bubbleNoSurface = true;
+          bubbleStatusBackup = statusEl.textContent;
+          statusEl.textContent = "No surface at this threshold \u2014 lower it";
+        } else if (hasSurface && bubbleNoSurface) {
+          bubbleNoSurface = false;
+          statusEl.textContent = bubbleStatusBackup;
+        }
+      }
+
+      function updateVolumeFromPositions(arr, signs) {
+        if (!volumeMesh || !volumeData) return;
+        const extent = Math.max(lastExtent, 1e-4);
+        volumeMesh.scale.setScalar(extent * 2.0);
+        volumeMesh.position.set(0, 0, 0);
+
+        const size = volumeResolution;
+        const size2 = size * size;
+        if (!volumeField || volumeField.length !== size2 * size) {
+          volumeField = new Float32Array(size2 * size);
+        } else {
+          volumeField.fill(0);
+        }
+        const count = Math.floor(arr.length / 3);
+        const step = Math.max(1, Math.floor(count / volumeSampleTarget));
+        const scale = (size - 1) / (2.0 * extent);
+        const useSigns = signs && signs.length === count;
+        const kernel = bubbleKernel;
+
+        for (let i = 0; i < count; i += step) {
+          const idx = i * 3;
+          const ix = Math.round((arr[idx + 0] + extent) * scale);
+          const iy = Math.round((arr[idx + 1] + extent) * scale);
+          const iz = Math.round((arr[idx + 2] + extent) * scale);
+          if (ix < 0 || ix >= size || iy < 0 || iy >= size || iz < 0 || iz >= size) {
+            continue;
+          }
+          const sign = useSigns && signs[i] < 0 ? -1 : 1;
+          for (let k = 0; k < kernel.length; k++) {
+            const x = ix + kernel[k][0];
+            const y = iy + kernel[k][1];
+            const z = iz + kernel[k][2];
+            if (x < 0 || x >= size || y < 0 || y >= size || z < 0 || z >= size) continue;
+            volumeField[x + size * y + size2 * z] += sign * kernel[k][3];
+          }
+        }
+
+        // Signed field packed into one byte per voxel: 128 is the nodal value,
+        // so opposite lobes stay separated by a dark gap after interpolation.
+        let maxAbs = 0.0;
+        for (let i = 0; i < volumeField.length; i++) {
+          const v = Math.abs(volumeField[i]);
+          if (v > maxAbs) maxAbs = v;
+        }
+        const invMax = maxAbs > 0 ? 1.0 / maxAbs : 0.0;
+        for (let i = 0; i < volumeField.length; i++) {
+          const v = volumeField[i] * invMax;
+          const s = Math.sign(v) * Math.sqrt(Math.abs(v));
+          volumeData[i] = Math.round(127.5 + 127.5 * s);
+        }
+        volumeTexture.needsUpdate = true;
+        bubbleDirty = false;
       }
 
       function updateMixUI() {
@@ -788,14 +1329,21 @@ const INDEX_HTML: &str = r##"<!doctype html>
         updateAnimUI();
       });
 
-      renderMode = localStorage.getItem("renderMode") || "dots";
+      if (!volumeSupported) {
+        const volumeOption = renderModeSelect.querySelector('option[value="volume"]');
+        if (volumeOption) {
+          volumeOption.disabled = true;
+          volumeOption.textContent = "Volume (needs WebGL2)";
+        }
+      }
+      renderMode = localStorage.getItem("renderMode") || serverDefaults.renderMode || "dots";
       renderModeSelect.value = renderMode;
       renderModeSelect.addEventListener("change", () => {
         updateRenderMode();
         fetchSamples().catch((err) => { statusEl.textContent = err.toString(); });
       });
 
-      dotColorMode = localStorage.getItem("dotColorMode") || "radial";
+      dotColorMode = localStorage.getItem("dotColorMode") || serverDefaults.dotColorMode || "radial";
       dotColorSelect.value = dotColorMode;
       dotColorSelect.addEventListener("change", () => {
         dotColorMode = dotColorSelect.value;
@@ -815,6 +1363,13 @@ const INDEX_HTML: &str = r##"<!doctype html>
         dotSizeInput.value = dotSize.toFixed(4);
       }
       updateDotSizeUI();
+      dotStyleSelect.value = dotStyle;
+      dotStyleSelect.addEventListener("change", () => {
+        dotStyle = dotStyleSelect.value;
+        localStorage.setItem("dotStyle", dotStyle);
+        applyDotStyle();
+      });
+
       dotSizeInput.addEventListener("input", () => {
         dotSize = Number(dotSizeInput.value);
         updateDotSizeUI();
@@ -835,7 +1390,12 @@ const INDEX_HTML: &str = r##"<!doctype html>
       applyBubbleQuality(bubbleQuality, false);
       bubbleQualityInput.addEventListener("input", () => {
         applyBubbleQuality(parseInt(bubbleQualityInput.value, 10));
+        const wanted = Math.min(Number(countInput.value), bubbleSampleTarget);
+        if (renderMode === "bubbles" && posAttr && posAttr.count < wanted) {
+          fetchSamples().catch((err) => { statusEl.textContent = err.toString(); });
+        }
       });
+      countInput.addEventListener("input", updateCountWarning);
 
       const storedIso = localStorage.getItem("bubbleIso");
       if (storedIso) {
@@ -867,11 +1427,11 @@ const INDEX_HTML: &str = r##"<!doctype html>
         localStorage.setItem("orbitalBasis", basisSelect.value);
         if (basisSelect.value === "real") {
           const lVal = Number(lInput.value);
-          if (lVal > 0 && Number(mInput.value) === 0) {
+          if (lVal > 0 && Number(mInput.value) === 0 && !mPinned) {
             mInput.value = Math.min(lVal, 3);
           }
           const l2Val = Number(l2Input.value);
-          if (l2Val > 0 && Number(m2Input.value) === 0) {
+          if (l2Val > 0 && Number(m2Input.value) === 0 && !m2Pinned) {
             m2Input.value = Math.min(l2Val, 3);
           }
           if (renderMode === "bubbles" && bubbleIsoFraction < 0.35) {
@@ -990,6 +1550,7 @@ const INDEX_HTML: &str = r##"<!doctype html>
         mixInput.disabled = !superMode;
         mixRow.style.display = superMode ? "flex" : "none";
         superPickRow.style.display = superMode ? "flex" : "none";
+        morphRow.style.display = orbitalMode ? "flex" : "none";
         if (!orbitalMode && !superMode) {
           orbitalRow.style.display = "none";
           superRow.style.display = "none";
@@ -1018,7 +1579,7 @@ const INDEX_HTML: &str = r##"<!doctype html>
           lInput.value = lStr;
           if (basisSelect.value === "real") {
             const lVal = Number(lInput.value);
-            if (lVal > 0 && Number(mInput.value) === 0) {
+            if (lVal > 0 && Number(mInput.value) === 0 && !mPinned) {
               mInput.value = Math.min(lVal, 3);
             }
           }
@@ -1070,6 +1631,7 @@ const INDEX_HTML: &str = r##"<!doctype html>
         fetchSamples().catch((err) => { statusEl.textContent = err.toString(); });
       });
       m2Input.addEventListener("change", () => {
+        m2Pinned = true;
         fetchSamples().catch((err) => { statusEl.textContent = err.toString(); });
       });
       nInput.addEventListener("change", () => {
@@ -1078,13 +1640,14 @@ const INDEX_HTML: &str = r##"<!doctype html>
       lInput.addEventListener("change", () => {
         if (basisSelect.value === "real") {
           const lVal = Number(lInput.value);
-          if (lVal > 0 && Number(mInput.value) === 0) {
+          if (lVal > 0 && Number(mInput.value) === 0 && !mPinned) {
             mInput.value = Math.min(lVal, 3);
           }
         }
         fetchSamples().catch((err) => { statusEl.textContent = err.toString(); });
       });
       mInput.addEventListener("change", () => {
+        mPinned = true;
         fetchSamples().catch((err) => { statusEl.textContent = err.toString(); });
       });
       mixInput.addEventListener("input", () => {
@@ -1272,6 +1835,7 @@ const INDEX_HTML: &str = r##"<!doctype html>
         btn.dataset.name = el.name.toLowerCase();
         btn.addEventListener("click", () => {
           setActiveElementByZ(el.Z);
+          pendingDefaultOrbital = true;
           elementModal.classList.remove("open");
           resetCamera();
           fetchSamples().catch((err) => { statusEl.textContent = err.toString(); });
@@ -1494,6 +2058,13 @@ const INDEX_HTML: &str = r##"<!doctype html>
         return new THREE.Color(r, g, b);
       }
 
+      function colorForShell(index, d, max) {
+        // Alternate warm/cool bands between radial nodes; fade outward so inner shells read as denser.
+        const fade = 1.0 - 0.6 * Math.min(d / max, 1.0);
+        const c = index % 2 === 0 ? [1.0, 0.62, 0.2] : [0.25, 0.7, 1.0];
+        return new THREE.Color(c[0] * fade, c[1] * fade, c[2] * fade);
+      }
+
       function colorForIntensity(value, maxValue) {
         const tRaw = maxValue > 0 ? Math.min(value / maxValue, 1) : 0;
         const t = Math.pow(tRaw, 0.4);
@@ -1654,6 +2225,13 @@ const INDEX_HTML: &str = r##"<!doctype html>
       }
 
       async function fetchSamples(forceTime = null, countOverride = null) {
+        if (morphActive) {
+          morphActive = false;
+          morphTarget = null;
+          animFrom = null;
+          animTo = null;
+        }
+        morphNote.style.display = "none";
         const n = Number(nInput.value);
         const l = Number(lInput.value);
         const m = Number(mInput.value);
@@ -1666,12 +2244,17 @@ const INDEX_HTML: &str = r##"<!doctype html>
         const wantMorph = animateEnabled && mode === "superposition";
         const wantPhaseMode = renderMode === "dots" && dotColorMode === "phase";
         const wantIntensityMode = renderMode === "dots" && dotColorMode === "intensity";
+        const wantShellsMode = renderMode === "dots" && dotColorMode === "shells";
         const wantPsi = animateEnabled && mode === "superposition" && (wantPhaseMode || wantIntensityMode);
-        const wantBubbles = renderMode === "bubbles";
+        const wantSigns = renderMode === "bubbles" || renderMode === "volume";
         let effectiveCount = count;
         if (wantMorph) {
           effectiveCount = count;
         }
+        if (renderMode === "bubbles") {
+          // Bubbles splat at most bubbleSampleTarget points into the grid; skip the rest.
+          effectiveCount = Math.min(count, bubbleSampleTarget);
+        }
         let n2 = Number(n2Input.value);
         let l2 = Number(l2Input.value);
         if ((!n2 || !l2) && orbitalSelectB.value) {
@@ -1689,14 +2272,20 @@ const INDEX_HTML: &str = r##"<!doctype html>
           statusEl.textContent = forceTime !== null ? "Animating..." : "Sampling...";
           setActiveElementByZ(z);
           const basisMode = (mode === "orbital" || mode === "superposition") ? basisSelect.value : "complex";
-          const colorModeParam = wantPhaseMode ? "phase" : (wantIntensityMode ? "intensity" : "radial");
-          const params = new URLSearchParams({ n, l, m, n2, l2, m2, z, count: effectiveCount, max, mode, mix, t, valence_style: valenceStyle, animated: wantPsi, bubble: wantBubbles, basis: basisMode, color_mode: colorModeParam });
+          const colorModeParam = wantPhaseMode
+            ? "phase"
+            : (wantIntensityMode ? "intensity" : (wantShellsMode ? "shells" : "radial"));
+          const params = new URLSearchParams({ n, l, m, n2, l2, m2, z, count: effectiveCount, max, mode, mix, t, valence_style: valenceStyle, animated: wantPsi, bubble: wantSigns, basis: basisMode, color_mode: colorModeParam });
+          if (wantPsi) {
+            params.set("session", superSession || "");
+          }
           const res = await fetch(`/samples?${params.toString()}`);
           if (!res.ok) {
             statusEl.textContent = "Error: " + res.status;
             return;
           }
           const data = await res.json();
+        superSession = data.session || null;
         if (data.mode && data.mode !== modeSelect.value) {
           modeSelect.value = data.mode;
           updateModeUI();
@@ -1706,6 +2295,20 @@ const INDEX_HTML: &str = r##"<!doctype html>
           lInput.value = data.l;
           mInput.value = data.m;
         }
+        if (pendingDefaultOrbital) {
+          pendingDefaultOrbital = false;
+          const def = data.default_orbital;
+          if (def) {
+            const orbitalView = data.mode === "orbital" || data.mode === "superposition";
+            const needsRefetch = orbitalView && (data.n !== def.n || data.l !== def.l);
+            nInput.value = def.n;
+            lInput.value = def.l;
+            mInput.value = Math.max(-def.l, Math.min(def.l, Number(mInput.value)));
+            if (needsRefetch) {
+              return fetchSamples(forceTime, countOverride);
+            }
+          }
+        }
         const element = ELEMENTS.find((el) => el.Z === data.z);
         const elementLabel = element ? `${element.symbol} ${element.name}` : `Z=${data.z}`;
         const sourceLabel = data.source === "openmx_lda"
@@ -1728,6 +2331,7 @@ const INDEX_HTML: &str = r##"<!doctype html>
           detail = `superposition ${orbA} + ${orbB} (mix ${mixValText})`;
         }
         statusEl.textContent = `${elementLabel} | ${detail} | count=${data.count} | ${sourceLabel}${note}${basisLabel}`;
+        bubbleNoSurface = false;
         updateOrbitalList(data.available_orbitals, data.selected_orbital, data.selected_orbital_b);
         if (data.mode === "superposition") {
           if (data.n2 !== null && data.n2 !== undefined) {
@@ -1778,6 +2382,9 @@ const INDEX_HTML: &str = r##"<!doctype html>
         const useIntensity = dotColorMode === "intensity"
           && Array.isArray(data.intensities)
           && data.intensities.length === data.samples.length;
+        const useShells = dotColorMode === "shells"
+          && Array.isArray(data.shell_index)
+          && data.shell_index.length === data.samples.length;
         let maxIntensity = 0.0;
         if (useIntensity) {
           for (let i = 0; i < data.intensities.length; i++) {
@@ -1795,6 +2402,9 @@ const INDEX_HTML: &str = r##"<!doctype html>
             c = colorForPhase(data.phases[i]);
           } else if (useIntensity) {
             c = colorForIntensity(data.intensities[i], maxIntensity);
+          } else if (useShells) {
+            const dist = Math.sqrt(p[0]*p[0] + p[1]*p[1] + p[2]*p[2]) * 0.1;
+            c = colorForShell(data.shell_index[i], dist, data.max_radius * 0.1);
           } else {
             const dist = Math.sqrt(p[0]*p[0] + p[1]*p[1] + p[2]*p[2]) * 0.1;
             c = colorForDistance(dist, data.max_radius * 0.1);
@@ -1885,16 +2495,7 @@ const INDEX_HTML: &str = r##"<!doctype html>
         colorAttr = geometry.getAttribute("color");
         baseColors = new Float32Array(colors);
 
-          const material = new THREE.PointsMaterial({
-            size: dotSize,
-            vertexColors: true,
-            transparent: true,
-            opacity: 0.6,
-            depthWrite: false,
-            blending: THREE.AdditiveBlending,
-            map: circleTexture,
-            alphaTest: 0.4,
-          });
+          const material = makeDotMaterial();
 
         points = new THREE.Points(geometry, material);
         group.add(points);
@@ -1922,6 +2523,97 @@ const INDEX_HTML: &str = r##"<!doctype html>
         }
       }
 
+      // Pair points by radius rank so shells map onto shells instead of scattering.
+      function radiusOrder(arr, count) {
+        const radii = new Float32Array(count);
+        for (let i = 0; i < count; i++) {
+          const x = arr[i * 3 + 0];
+          const y = arr[i * 3 + 1];
+          const z = arr[i * 3 + 2];
+          radii[i] = x * x + y * y + z * z;
+        }
+        const order = Array.from({ length: count }, (_, i) => i);
+        order.sort((a, b) => radii[a] - radii[b]);
+        return order;
+      }
+
+      async function morphToOrbital() {
+        if (!posAttr || modeSelect.value !== "orbital") return;
+        const target = {
+          n: Number(morphNInput.value),
+          l: Number(morphLInput.value),
+          m: Number(morphMInput.value),
+        };
+        const count = posAttr.count;
+        const basisMode = basisSelect.value;
+        const params = new URLSearchParams({
+          n: target.n, l: target.l, m: target.m, z: Number(zInput.value), count, max: Number(maxInput.value),
+          mode: "orbital", basis: basisMode, color_mode: "radial",
+        });
+        statusEl.textContent = "Sampling morph target...";
+        const res = await fetch(`/samples?${params.toString()}`);
+        if (!res.ok) {
+          statusEl.textContent = "Error: " + res.status;
+          return;
+        }
+        const data = await res.json();
+        if (!data.samples || data.samples.length === 0) {
+          statusEl.textContent = data.note ? `Morph target unavailable: ${data.note}` : "Morph target unavailable";
+          return;
+        }
+        const targetCount = data.samples.length;
+        const targetArr = new Float32Array(targetCount * 3);
+        for (let i = 0; i < targetCount; i++) {
+          const p = data.samples[i];
+          targetArr[i * 3 + 0] = p[0] * 0.1;
+          targetArr[i * 3 + 1] = p[1] * 0.1;
+          targetArr[i * 3 + 2] = p[2] * 0.1;
+        }
+        const fromOrder = radiusOrder(posAttr.array, count);
+        const toOrder = radiusOrder(targetArr, targetCount);
+        const positions = new Float32Array(count * 3);
+        for (let k = 0; k < count; k++) {
+          const src = fromOrder[k] * 3;
+          const dst = toOrder[Math.floor(k * targetCount / count)] * 3;
+          positions[src + 0] = targetArr[dst + 0];
+          positions[src + 1] = targetArr[dst + 1];
+          positions[src + 2] = targetArr[dst + 2];
+        }
+
+        spinPhi0 = null;
+        spinRho = null;
+        spinOmega = null;
+        spinZ = null;
+        lastSigns = null;
+        animFrom = posAttr.array.slice(0);
+        animTo = positions;
+        animStart = performance.now();
+        animDurationMs = Math.max(500, Number(morphSecondsInput.value) * 1000 || 3000);
+        morphTarget = { n: data.n, l: data.l, m: data.m };
+        morphActive = true;
+        morphNote.style.display = "block";
+        statusEl.textContent = `Morphing n=${nInput.value} l=${lInput.value} m=${mInput.value} -> n=${data.n} l=${data.l} m=${data.m} (illustrative only)`;
+      }
+
+      function finishMorph() {
+        const target = morphTarget;
+        morphActive = false;
+        morphTarget = null;
+        animFrom = null;
+        animTo = null;
+        if (!target) return;
+        nInput.value = target.n;
+        lInput.value = target.l;
+        mInput.value = target.m;
+        mPinned = true;
+        // Resample the target so colors follow the selected color mode and the status line resets.
+        fetchSamples().catch((err) => { statusEl.textContent = err.toString(); });
+      }
+
+      morphGoButton.addEventListener("click", () => {
+        morphToOrbital().catch((err) => { statusEl.textContent = err.toString(); });
+      });
+
       document.getElementById("go").addEventListener("click", () => {
         superpositionTime = 0.0;
         superFetchInFlight = false;
@@ -1935,6 +2627,7 @@ const INDEX_HTML: &str = r##"<!doctype html>
       zInput.addEventListener("change", () => {
         const z = Number(zInput.value);
         setActiveElementByZ(z);
+        pendingDefaultOrbital = true;
         resetCamera();
         fetchSamples().catch((err) => { statusEl.textContent = err.toString(); });
       });
@@ -1952,6 +2645,21 @@ const INDEX_HTML: &str = r##"<!doctype html>
         const dt = Math.min((now - lastTime) / 1000, 0.05);
         lastTime = now;
 
+        if (morphActive && animTo && animFrom && posAttr) {
+          const t = Math.min((now - animStart) / Math.max(animDurationMs, 1), 1);
+          const k = t * t * (3 - 2 * t);
+          const arr = posAttr.array;
+          for (let i = 0; i < arr.length; i++) {
+            arr[i] = animFrom[i] + (animTo[i] - animFrom[i]) * k;
+          }
+          posAttr.needsUpdate = true;
+          if (renderMode !== "dots") {
+            bubbleDirty = true;
+          }
+          if (t >= 1) {
+            finishMorph();
+          }
+        }
         if (modeSelect.value === "superposition" && animateEnabled) {
           superpositionTime += dt * animSpeed;
           if (animTo && animFrom && posAttr) {
@@ -1962,7 +2670,7 @@ const INDEX_HTML: &str = r##"<!doctype html>
               arr[i] = animFrom[i] + (animTo[i] - animFrom[i]) * k;
             }
             posAttr.needsUpdate = true;
-            if (renderMode === "bubbles") {
+            if (renderMode !== "dots") {
               bubbleDirty = true;
             }
             if (t >= 1) {
@@ -2008,9 +2716,13 @@ const INDEX_HTML: &str = r##"<!doctype html>
           }
           posAttr.needsUpdate = true;
         }
-        if (renderMode === "bubbles" && posAttr && bubbleDirty && (now - lastBubbleUpdate) > bubbleUpdateInterval) {
+        if (renderMode !== "dots" && posAttr && bubbleDirty && (now - lastBubbleUpdate) > bubbleUpdateInterval) {
           lastBubbleUpdate = now;
-          updateBubblesFromPositions(posAttr.array, lastSigns);
+          if (renderMode === "volume") {
+            updateVolumeFromPositions(posAttr.array, lastSigns);
+          } else {
+            updateBubblesFromPositions(posAttr.array, lastSigns);
+          }
         }
         group.scale.setScalar(1.0);
 
@@ -2047,6 +2759,10 @@ const INDEX_HTML: &str = r##"<!doctype html>
             }
           }
         }
+        if (renderMode === "dots" && dotStyle === "spheres" && points && (now - lastSortTime) > 30) {
+          lastSortTime = now;
+          sortPointsBackToFront();
+        }
         renderer.render(scene, camera);
       }
 
@@ -2391,6 +3107,7 @@ const INFO_HTML: &str = r##"<!doctype html>
               <p>Bubbles mode converts the point cloud into a smooth density grid and extracts an isosurface. The threshold control sets which density fraction becomes the surface. Lower thresholds show more diffuse lobes. Higher thresholds highlight the dense core.</p>
               <p>The surface is built from a kernel smoothed density, so it is an approximation of |psi|^2 and depends on grid resolution as well as the threshold.</p>
               <p>The Quality slider adjusts bubble grid resolution and the number of samples used to build the density field. Higher quality looks smoother but costs more performance.</p>
+              <p>Volume mode (experimental) bins the same samples into a signed 64^3 grid, uploads it as a 3D texture, and ray-marches it in a shader. Nodal surfaces show up as dark gaps between red and blue lobes. It needs WebGL2; without it the view falls back to dots.</p>
             </div>
             <div class="grid-2">
               <div class="card">
@@ -2410,6 +3127,7 @@ const INFO_HTML: &str = r##"<!doctype html>
               <p>Dots can be colored by radial distance or by the complex phase of the wavefunction. Phase is not observable directly in |psi|^2, but it is essential for interference and superposition. Bubbles use red and blue to indicate positive and negative regions of psi when sign is defined.</p>
               <p>Phase hue mapping uses h = (phase + pi) / (2 pi) * 360 degrees with saturation and value set to 0.95. This means phase = -pi or +pi maps to red, phase = -pi/2 maps near yellow-green, phase = 0 maps to cyan, and phase = +pi/2 maps near purple-blue.</p>
               <p>Intensity mode maps |psi|^2 to a heat-style gradient from deep violet through red and gold to white, emphasizing the highest probability density regions.</p>
+              <p>Radial shells mode (single orbitals) splits the cloud at the radial nodes of R(r) and alternates amber and blue bands between them, so each region of constant radial sign is easy to pick out in 3D.</p>
             </div>
             <div class="card">
               <div class="diagram-grid">
@@ -2441,6 +3159,7 @@ const INFO_HTML: &str = r##"<!doctype html>
               <pre>|psi|^2 = |a psi1|^2 + |b psi2|^2 + 2 Re[a b* psi1 psi2* exp(-i DeltaE t)]</pre>
               <p>The interference term produces real spatial motion in the density. In the hydrogenic model, states with the same n are degenerate, so DeltaE = 0 and the density does not evolve. Choose orbitals with different n for visible dynamics.</p>
               <p>The oscillation period is T = 2 pi / DeltaE in atomic units. Larger energy gaps yield faster beat motion.</p>
+              <p>The Morph to control in single orbital mode is different: it pairs points of two clouds by radius and slides them in straight lines. It is only a way to compare shapes; an electron moving between orbitals passes through superpositions like the ones above.</p>
             </div>
             <div class="card">
               <div class="diagram-grid">
@@ -2464,7 +3183,7 @@ const INFO_HTML: &str = r##"<!doctype html>
                   <tr><th>Control</th><th>Meaning</th></tr>
                 </thead>
                 <tbody>
-                  <tr><td>Render</td><td>Dots shows samples, Bubbles shows an isosurface.</td></tr>
+                  <tr><td>Render</td><td>Dots shows samples, Bubbles shows an isosurface, Volume ray-marches a glowing density cloud.</td></tr>
                   <tr><td>Mode</td><td>Total density, valence density, single orbital, or superposition.</td></tr>
                   <tr><td>Basis</td><td>Complex (m) or real chemistry combinations for orbital lobes.</td></tr>
                   <tr><td>n, l, m</td><td>Quantum numbers that define a single orbital.</td></tr>
@@ -2472,7 +3191,9 @@ const INFO_HTML: &str = r##"<!doctype html>
                   <tr><td>max</td><td>Maximum radius for sampling. Larger values show more diffuse tails.</td></tr>
                   <tr><td>mix</td><td>Superposition weight between orbital A and B.</td></tr>
                   <tr><td>Animated</td><td>Enables time dependent evolution in superposition mode.</td></tr>
+                  <tr><td>Morph to</td><td>Single orbital mode: slides the current cloud into another orbital's cloud over the given seconds. A teaching aid, not a physical transition.</td></tr>
                   <tr><td>Speed</td><td>Scales the animation time variable.</td></tr>
+                  <tr><td>Dot style</td><td>Glow adds overlapping dots together. Transparent spheres blend normally and are re-sorted back to front as the camera moves, so near dots correctly cover far ones.</td></tr>
                   <tr><td>Threshold</td><td>Bubble isosurface level as a fraction of peak density.</td></tr>
                   <tr><td>Quality</td><td>Bubble grid resolution and sample count preset.</td></tr>
                 </tbody>
@@ -2536,14 +3257,157 @@ const INFO_HTML: &str = r##"<!doctype html>
 </html>
 "##;
 
+const RENDER_MODES: [&str; 3] = ["dots", "bubbles", "volume"];
+const DOT_COLOR_MODES: [&str; 4] = ["radial", "phase", "intensity", "shells"];
+
+fn allowed_choice(
+    value: Option<&str>,
+    allowed: &[&'static str],
+    fallback: &'static str,
+) -> &'static str {
+    value
+        .and_then(|v| {
+            let v = v.trim().to_lowercase();
+            allowed.iter().copied().find(|a| *a == v)
+        })
+        .unwrap_or(fallback)
+}
+
+fn render_index_html(render_mode: Option<&str>, color_mode: Option<&str>) -> String {
+    let defaults = serde_json::json!({
+        "renderMode": allowed_choice(render_mode, &RENDER_MODES, "dots"),
+        "dotColorMode": allowed_choice(color_mode, &DOT_COLOR_MODES, "radial"),
+    });
+    INDEX_HTML.replace("__SERVER_DEFAULTS__", &defaults.to_string())
+}
+
 async fn index() -> impl IntoResponse {
-    Html(INDEX_HTML)
+    let render_mode = std::env::var("DEFAULT_RENDER_MODE").ok();
+    let color_mode = std::env::var("DEFAULT_COLOR_MODE").ok();
+    Html(render_index_html(render_mode.as_deref(), color_mode.as_deref()))
 }
 
 async fn info() -> impl IntoResponse {
     Html(INFO_HTML)
 }
 
+async fn radial(Query(q): Query<RadialQuery>) -> Response {
+    let n = q.n.unwrap_or(1).max(1);
+    let l = q.l.unwrap_or(0);
+    let z = q.z.unwrap_or(1).clamp(1, 118);
+    let max_radius = q.max.unwrap_or(20.0).clamp(1.0, 200.0);
+    let points = q.points.unwrap_or(400).clamp(16, 4000);
+    let form = RadialForm::from_query(q.radial_form.as_deref());
+    let rs = build_radial_grid(max_radius, points);
+
+    let mut note: Option<String> = None;
+    if z != 1 {
+        if let Some(symbol) = symbol_for_z(z) {
+            if let Ok(data) = load_lda_element(symbol).await {
+                if let Some((orbital, exact)) = select_lda_orbital(&data, n, l) {
+                    let note = if exact {
+                        None
+                    } else {
+                        Some(format!("requested n/l not in dataset; using {}", orbital.label))
+                    };
+                    let values = radial_form_values(
+                        &rs,
+                        &orbital.radial_r,
+                        &orbital.radial_rfn,
+                        RadialKind::R,
+                        form,
+                    );
+                    let out = RadialResponse {
+                        n: orbital.n,
+                        l: orbital.l,
+                        z,
+                        source: "openmx_lda".to_string(),
+                        radial_form: form.as_str().to_string(),
+                        note,
+                        r: rs,
+                        values,
+                    };
+                    return Json(out).into_response();
+                }
+            }
+            if let Ok(data) = load_element_data(symbol, z).await {
+                if let Some((orbital, exact)) = select_pslib_orbital(&data, n, l) {
+                    let note = if exact {
+                        None
+                    } else {
+                        Some(format!("requested n/l not in dataset; using {}", orbital.label))
+                    };
+                    let values = radial_form_values(
+                        &rs,
+                        &orbital.radial_r,
+                        &orbital.radial_chi,
+                        RadialKind::Chi,
+                        form,
+                    );
+                    let out = RadialResponse {
+                        n: orbital.n,
+                        l: orbital.l,
+                        z,
+                        source: "pslibrary".to_string(),
+                        radial_form: form.as_str().to_string(),
+                        note,
+                        r: rs,
+                        values,
+                    };
+                    return Json(out).into_response();
+                }
+            }
+            note = Some("orbital not available in datasets; using hydrogenic".to_string());
+        }
+    }
+
+    if l >= n {
+        return (StatusCode::BAD_REQUEST, "invalid quantum numbers").into_response();
+    }
+    // Hydrogen-like ion: R^Z_nl(r) = Z^(3/2) R^H_nl(Z r).
+    let z_f = z as f32;
+    let hydrogenic: Vec<f32> = rs
+        .iter()
+        .map(|&r| z_f.powf(1.5) * radial_wavefunction(z_f * r, n, l))
+        .collect();
+    let values = radial_form_values(&rs, &rs, &hydrogenic, RadialKind::R, form);
+    let out = RadialResponse {
+        n,
+        l,
+        z,
+        source: "hydrogenic".to_string(),
+        radial_form: form.as_str().to_string(),
+        note,
+        r: rs,
+        values,
+    };
+    Json(out).into_response()
+}
+
+async fn slice_png(Query(q): Query<ImageQuery>) -> Response {
+    let (width, height) = image_dims(&q);
+    let basis = AngularBasis::from_query(q.basis.as_deref());
+    let qn = match QuantumNumbers::new(q.n.unwrap_or(2), q.l.unwrap_or(1), q.m.unwrap_or(0)) {
+        Some(qn) => qn,
+        None => return (StatusCode::BAD_REQUEST, "invalid quantum numbers").into_response(),
+    };
+    let z = q.z.unwrap_or(1).clamp(1, 118);
+    let extent = q.max.unwrap_or(20.0).clamp(1.0, 200.0);
+    let grid = slice_grid(qn, z, extent, width, height, basis);
+    png_response(&grid, width, height, Colormap::from_query(q.colormap.as_deref()))
+}
+
+async fn angular_png(Query(q): Query<ImageQuery>) -> Response {
+    let (width, height) = image_dims(&q);
+    let basis = AngularBasis::from_query(q.basis.as_deref());
+    let qn = match QuantumNumbers::new(q.n.unwrap_or(2), q.l.unwrap_or(1), q.m.unwrap_or(0)) {
+        Some(qn) => qn,
+        None => return (StatusCode::BAD_REQUEST, "invalid quantum numbers").into_response(),
+    };
+    let grid = angular_grid(qn.l, qn.m_l, width, height, basis);
+    png_response(&grid, width, height, Colormap::from_query(q.colormap.as_deref()))
+}
+
 async fn three_module() -> impl IntoResponse {
     ([(header::CONTENT_TYPE, "application/javascript")], THREE_JS)
 }
@@ -2566,12 +3430,19 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
         q.animated.unwrap_or(false) && requested_mode == ViewMode::Superposition;
     let want_phase = matches!(q.color_mode.as_deref(), Some("phase"));
     let want_intensity = matches!(q.color_mode.as_deref(), Some("intensity"));
+    let want_shells = matches!(q.color_mode.as_deref(), Some("shells"));
     let bubble = q.bubble.unwrap_or(false);
+    let want_mean_radius = q.mean_radius.unwrap_or(false);
+    let want_provenance = q.provenance.unwrap_or(false);
+    let want_profile = q.profile.unwrap_or(false);
     let n2 = q.n2.unwrap_or(n);
     let l2 = q.l2.unwrap_or(l);
     let m2 = q.m2.unwrap_or(0);
     let mix = q.mix.unwrap_or(0.5).clamp(0.05, 0.95);
     let time = q.t.unwrap_or(0.0);
+    // Skeleton reuse only holds when every proposal is kept (psi mode); otherwise
+    // the accepted set depends on t.
+    let session_token = if want_super_psi { q.session.as_deref() } else { None };
 
     let mut note: Option<String> = None;
     if let Some(symbol) = symbol_for_z(z) {
@@ -2579,7 +3450,9 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
             !(z == 1 && (requested_mode == ViewMode::Orbital || requested_mode == ViewMode::Superposition));
         if use_lda {
             if let Ok(data) = load_lda_element(symbol).await {
+                let data_file = if want_provenance { Some(data.data_file.clone()) } else { None };
                 let available = lda_available_orbitals(&data);
+                let default_orbital = lda_default_orbital(&data);
                 let max_r = data.r_max.min(max_radius);
 
                 match requested_mode {
@@ -2634,6 +3507,7 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                                 source: "openmx_lda".to_string(),
                                 note: Some(mode_note),
                                 available_orbitals: available,
+                                default_orbital,
                                 selected_orbital: None,
                                 selected_orbital_b: None,
                                 mix: None,
@@ -2644,8 +3518,14 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                                 signs: if bubble { Some(vec![1; sign_count]) } else { None },
                                 phases: None,
                                 intensities: None,
+                                shell_index: None,
+                                empirical_mean_radius: None,
+                                length_unit: None,
+                                session: None,
+                                data_file,
+                                profile: None,
                             };
-                            return Json(out).into_response();
+                            return Json(out.with_mean_radius(want_mean_radius)).into_response();
                         }
                     }
                     ViewMode::Valence => {
@@ -2741,6 +3621,7 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                                 source: "openmx_lda".to_string(),
                                 note: Some(mode_note),
                                 available_orbitals: available,
+                                default_orbital,
                                 selected_orbital: None,
                                 selected_orbital_b: None,
                                 mix: None,
@@ -2751,8 +3632,14 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                                 signs: if bubble { Some(vec![1; sign_count]) } else { None },
                                 phases: None,
                                 intensities: None,
+                                shell_index: None,
+                                empirical_mean_radius: None,
+                                length_unit: None,
+                                session: None,
+                                data_file,
+                                profile: None,
                             };
-                            return Json(out).into_response();
+                            return Json(out.with_mean_radius(want_mean_radius)).into_response();
                         }
                     }
                     ViewMode::Orbital => {
@@ -2763,8 +3650,9 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                             let radial_r_sign = radial_r.clone();
                             let radial_val_sign = radial_val.clone();
                             let l_used = orbital.l;
-                            let samples = tokio::task::spawn_blocking(move || {
-                                generate_orbital_samples_from_radial(
+                            let (samples, profile) = tokio::task::spawn_blocking(move || {
+                                let mut profile = SamplingProfile::default();
+                                let samples = generate_orbital_samples_from_radial(
                                     &radial_r,
                                     &radial_val,
                                     l_used,
@@ -2773,7 +3661,9 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                                     max_r,
                                     RadialKind::R,
                                     basis,
-                                )
+                                    &mut profile,
+                                );
+                                (samples, profile)
                             })
                             .await
                             .unwrap_or_default();
@@ -2816,6 +3706,12 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                             } else {
                                 None
                             };
+                            let shell_index = if want_shells {
+                                let nodes = radial_nodes(&radial_r_sign, &radial_val_sign);
+                                Some(shell_indices(&samples, &nodes))
+                            } else {
+                                None
+                            };
                             let used_label = orbital.label.clone();
                             let mode_note = if exact {
                                 format!("OpenMX LDA {}", used_label)
@@ -2837,6 +3733,7 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                                 source: "openmx_lda".to_string(),
                                 note: Some(mode_note),
                                 available_orbitals: available,
+                                default_orbital,
                                 selected_orbital: Some(used_label),
                                 selected_orbital_b: None,
                                 mix: None,
@@ -2847,8 +3744,14 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                                 signs,
                                 phases,
                                 intensities,
+                                shell_index,
+                                empirical_mean_radius: None,
+                                length_unit: None,
+                                session: None,
+                                data_file,
+                                profile: if want_profile { Some(profile) } else { None },
                             };
-                            return Json(out).into_response();
+                            return Json(out.with_mean_radius(want_mean_radius)).into_response();
                         }
                         note = Some("orbital not available in LDA dataset".to_string());
                     }
@@ -2866,23 +3769,41 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                             };
                             let orb_a_cl = orb_a.clone();
                             let orb_b_cl = orb_b.clone();
-                            let (samples, psi1, psi2) = tokio::task::spawn_blocking(move || {
-                                generate_superposition_samples_lda(
-                                    &orb_a_cl,
-                                    &orb_b_cl,
-                                    m_a,
-                                    m_b,
-                                    mix,
-                                    time,
-                                    count,
-                                    max_r,
-                                    delta_e,
-                                    want_super_psi,
-                                    basis,
-                                )
-                            })
-                            .await
-                            .unwrap_or_default();
+                            let session_key = skeleton_key("openmx_lda", z, &q, mix, count, max_r, basis);
+                            let reused = session_token
+                                .and_then(|t| reuse_skeleton(t, &session_key).map(|s| (t.to_string(), s)));
+                            let (session, (samples, psi1, psi2), profile) = match reused {
+                                Some((token, skeleton)) => {
+                                    (Some(token), take_skeleton(skeleton), None)
+                                }
+                                None => {
+                                    let (skeleton, profile) = tokio::task::spawn_blocking(move || {
+                                        let mut profile = SamplingProfile::default();
+                                        let skeleton = generate_superposition_samples_lda(
+                                            &orb_a_cl,
+                                            &orb_b_cl,
+                                            m_a,
+                                            m_b,
+                                            mix,
+                                            time,
+                                            count,
+                                            max_r,
+                                            delta_e,
+                                            want_super_psi,
+                                            basis,
+                                            &mut profile,
+                                        );
+                                        (skeleton, profile)
+                                    })
+                                    .await
+                                    .unwrap_or_default();
+                                    let skeleton = Arc::new(skeleton);
+                                    let session = session_token.and_then(|_| {
+                                        store_skeleton(session_key, Arc::clone(&skeleton))
+                                    });
+                                    (session, take_skeleton(skeleton), Some(profile))
+                                }
+                            };
                             let signs = if bubble {
                                 Some(signs_from_superposition_lda(
                                     &samples,
@@ -2953,6 +3874,7 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                                 source: "openmx_lda".to_string(),
                                 note: Some(mode_note),
                                 available_orbitals: available,
+                                default_orbital,
                                 selected_orbital: Some(orb_a.label.clone()),
                                 selected_orbital_b: Some(orb_b.label.clone()),
                                 mix: Some(mix),
@@ -2963,8 +3885,14 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                                 signs,
                                 phases,
                                 intensities,
+                                shell_index: None,
+                                empirical_mean_radius: None,
+                                length_unit: None,
+                                session,
+                                data_file,
+                                profile: if want_profile { profile } else { None },
                             };
-                            return Json(out).into_response();
+                            return Json(out.with_mean_radius(want_mean_radius)).into_response();
                         }
                         note = Some("superposition orbitals not available".to_string());
                     }
@@ -2978,6 +3906,7 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
     if requested_mode == ViewMode::Orbital && z != 1 {
         if let Some(symbol) = symbol_for_z(z) {
             if let Ok(data) = load_element_data(symbol, z).await {
+                let data_file = if want_provenance { Some(data.data_file.clone()) } else { None };
                 let available = data
                     .orbitals
                     .iter()
@@ -2987,6 +3916,7 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                         l: o.l,
                     })
                     .collect::<Vec<_>>();
+                let default_orbital = pslib_default_orbital(&data);
 
                 if let Some((orbital, exact)) = select_pslib_orbital(&data, n, l) {
                     let max_r = data.r_max.min(max_radius);
@@ -2996,8 +3926,9 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                     let radial_r_sign = radial_r.clone();
                     let radial_val_sign = radial_val.clone();
                     let l_used = orbital.l;
-                    let samples = tokio::task::spawn_blocking(move || {
-                        generate_orbital_samples_from_radial(
+                    let (samples, profile) = tokio::task::spawn_blocking(move || {
+                        let mut profile = SamplingProfile::default();
+                        let samples = generate_orbital_samples_from_radial(
                             &radial_r,
                             &radial_val,
                             l_used,
@@ -3006,7 +3937,9 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                             max_r,
                             RadialKind::Chi,
                             basis,
-                        )
+                            &mut profile,
+                        );
+                        (samples, profile)
                     })
                     .await
                     .unwrap_or_default();
@@ -3049,6 +3982,12 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                     } else {
                         None
                     };
+                    let shell_index = if want_shells {
+                        let nodes = radial_nodes(&radial_r_sign, &radial_val_sign);
+                        Some(shell_indices(&samples, &nodes))
+                    } else {
+                        None
+                    };
                     let used_label = orbital.label.clone();
                     let mode_note = if exact {
                         format!("PSlibrary {}", used_label)
@@ -3070,6 +4009,7 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                         source: "pslibrary".to_string(),
                         note: Some(mode_note),
                         available_orbitals: available,
+                        default_orbital,
                         selected_orbital: Some(used_label),
                         selected_orbital_b: None,
                         mix: None,
@@ -3080,8 +4020,14 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                         signs,
                         phases,
                         intensities,
+                        shell_index,
+                        empirical_mean_radius: None,
+                        length_unit: None,
+                        session: None,
+                        data_file,
+                        profile: if want_profile { Some(profile) } else { None },
                     };
-                    return Json(out).into_response();
+                    return Json(out.with_mean_radius(want_mean_radius)).into_response();
                 }
 
                 note = Some("orbital not available in dataset".to_string());
@@ -3100,6 +4046,7 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                     source: "pslibrary".to_string(),
                     note,
                     available_orbitals: available,
+                    default_orbital,
                     selected_orbital: None,
                     selected_orbital_b: None,
                     mix: None,
@@ -3110,8 +4057,14 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                     signs: None,
                     phases: None,
                     intensities: None,
+                    shell_index: None,
+                    empirical_mean_radius: None,
+                    length_unit: None,
+                    session: None,
+                    data_file,
+                    profile: None,
                 };
-                return Json(out).into_response();
+                return Json(out.with_mean_radius(want_mean_radius)).into_response();
             } else {
                 note = Some("dataset unavailable; using hydrogenic".to_string());
             }
@@ -3125,21 +4078,36 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
             let e1 = hydrogenic_energy(q1.n);
             let e2 = hydrogenic_energy(q2.n);
             let delta_e = e2 - e1;
-            let (samples, psi1, psi2) = tokio::task::spawn_blocking(move || {
-                generate_superposition_samples_hydrogenic(
-                    q1,
-                    q2,
-                    mix,
-                    time,
-                    count,
-                    max_radius,
-                    delta_e,
-                    want_super_psi,
-                    basis,
-                )
-            })
-            .await
-            .unwrap_or_default();
+            let session_key = skeleton_key("hydrogenic", z, &q, mix, count, max_radius, basis);
+            let reused = session_token
+                .and_then(|t| reuse_skeleton(t, &session_key).map(|s| (t.to_string(), s)));
+            let (session, (samples, psi1, psi2), profile) = match reused {
+                Some((token, skeleton)) => (Some(token), take_skeleton(skeleton), None),
+                None => {
+                    let (skeleton, profile) = tokio::task::spawn_blocking(move || {
+                        let mut profile = SamplingProfile::default();
+                        let skeleton = generate_superposition_samples_hydrogenic(
+                            q1,
+                            q2,
+                            mix,
+                            time,
+                            count,
+                            max_radius,
+                            delta_e,
+                            want_super_psi,
+                            basis,
+                            &mut profile,
+                        );
+                        (skeleton, profile)
+                    })
+                    .await
+                    .unwrap_or_default();
+                    let skeleton = Arc::new(skeleton);
+                    let session = session_token
+                        .and_then(|_| store_skeleton(session_key, Arc::clone(&skeleton)));
+                    (session, take_skeleton(skeleton), Some(profile))
+                }
+            };
             let signs = if bubble {
                 Some(signs_from_superposition_hydrogenic(
                     &samples,
@@ -3179,12 +4147,15 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
             } else {
                 None
             };
-            let inv_z = 1.0 / z as f32;
-            let scaled_max = if z > 1 { max_radius * inv_z } else { max_radius };
-            let scaled_samples = if z > 1 {
+            let mean_radius =
+                hydrogenic_mean_radius(q1.n, q1.l).max(hydrogenic_mean_radius(q2.n, q2.l));
+            let (length_scale, scale_note) = hydrogenic_display_scale(z, mean_radius, max_radius);
+            let length_unit = scale_note.as_ref().map(|_| "a0/Z".to_string());
+            let scaled_max = max_radius * length_scale;
+            let scaled_samples = if length_scale != 1.0 {
                 samples
                     .into_iter()
-                    .map(|p| [p[0] * inv_z, p[1] * inv_z, p[2] * inv_z])
+                    .map(|p| [p[0] * length_scale, p[1] * length_scale, p[2] * length_scale])
                     .collect::<Vec<_>>()
             } else {
                 samples
@@ -3193,7 +4164,10 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
             if delta_e.abs() < 1e-6 {
                 note_text.push_str(" | same n -> no time evolution");
             }
-            if z > 1 {
+            if let Some(warning) = scale_note {
+                note_text.push_str(" | ");
+                note_text.push_str(&warning);
+            } else if z > 1 {
                 note_text.push_str(" | hydrogenic approximation scaled by Z");
             }
             let out = SampleResponse {
@@ -3211,6 +4185,7 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                 source: "hydrogenic".to_string(),
                 note: Some(note_text),
                 available_orbitals: Vec::new(),
+                default_orbital: None,
                 selected_orbital: None,
                 selected_orbital_b: None,
                 mix: Some(mix),
@@ -3221,8 +4196,14 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                 signs,
                 phases,
                 intensities,
+                shell_index: None,
+                empirical_mean_radius: None,
+                length_unit,
+                session,
+                data_file: None,
+                profile: if want_profile { profile } else { None },
             };
-            return Json(out).into_response();
+            return Json(out.with_mean_radius(want_mean_radius)).into_response();
         } else {
             note = Some("invalid quantum numbers for superposition".to_string());
         }
@@ -3252,6 +4233,7 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                 source: "hydrogenic".to_string(),
                 note,
                 available_orbitals: Vec::new(),
+                default_orbital: None,
                     selected_orbital: None,
                     selected_orbital_b: None,
                     mix: None,
@@ -3262,8 +4244,14 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
                     signs: None,
                     phases: None,
                     intensities: None,
+                    shell_index: None,
+                    empirical_mean_radius: None,
+                    length_unit: None,
+                    session: None,
+                    data_file: None,
+                    profile: None,
                 };
-            return Json(empty).into_response();
+            return Json(empty.with_mean_radius(want_mean_radius)).into_response();
         }
     };
 
@@ -3300,13 +4288,46 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
     } else {
         None
     };
-    let inv_z = 1.0 / z as f32;
+    let shell_index = if want_shells {
+        let nodes = hydrogenic_radial_nodes(qn.n, qn.l, max_radius);
+        Some(shell_indices(
+            &raw.iter().map(|(x, y, z)| [*x, *y, *z]).collect::<Vec<_>>(),
+            &nodes,
+        ))
+    } else {
+        None
+    };
+    let mut out = hydrogenic_orbital_response(qn, z, count, max_radius, raw, note);
+    out.signs = signs;
+    out.phases = phases;
+    out.intensities = intensities;
+    out.shell_index = shell_index;
+    Json(out.with_mean_radius(want_mean_radius)).into_response()
+}
+
+fn hydrogenic_orbital_response(
+    qn: QuantumNumbers,
+    z: u32,
+    count: usize,
+    max_radius: f32,
+    raw: Vec<(f32, f32, f32)>,
+    mut note: Option<String>,
+) -> SampleResponse {
+    let (length_scale, scale_note) =
+        hydrogenic_display_scale(z, hydrogenic_mean_radius(qn.n, qn.l), max_radius);
+    let length_unit = scale_note.as_ref().map(|_| "a0/Z".to_string());
+    if let Some(warning) = scale_note {
+        note = Some(match note {
+            Some(existing) => format!("{existing} | {warning}"),
+            None => warning,
+        });
+    }
     let samples = raw
         .into_iter()
-        .map(|(x, y, z_pos)| [x * inv_z, y * inv_z, z_pos * inv_z])
+        .map(|(x, y, z_pos)| [x * length_scale, y * length_scale, z_pos * length_scale])
         .collect();
 
-    let out = SampleResponse {
+    SampleResponse {
         n: qn.n,
         l: qn.l,
         m: qn.m_l,
@@ -3315,12 +4336,13 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
         m2: None,
         z,
         count,
-        max_radius,
+        max_radius: max_radius * length_scale,
         samples,
         mode: ViewMode::Orbital.as_str().to_string(),
         source: "hydrogenic".to_string(),
         note,
         available_orbitals: Vec::new(),
+        default_orbital: None,
         selected_orbital: None,
         selected_orbital_b: None,
         mix: None,
@@ -3328,11 +4350,139 @@ async fn samples(Query(q): Query<SampleQuery>) -> impl IntoResponse {
         psi1: None,
         psi2: None,
         delta_e: None,
-        signs,
-        phases,
-        intensities,
-    };
-    Json(out).into_response()
+        signs: None,
+        phases: None,
+        intensities: None,
+        shell_index: None,
+        empirical_mean_radius: None,
+        length_unit,
+        session: None,
+        data_file: None,
+        profile: None,
+    }
+}
+
+// Chi data already is u(r); R(r) is recovered as u / r.
+fn radial_form_values(
+    grid: &[f32],
+    rs: &[f32],
+    vs: &[f32],
+    kind: RadialKind,
+    form: RadialForm,
+) -> Vec<f32> {
+    let mut out: Vec<f32> = grid
+        .iter()
+        .map(|&r| {
+            let v = interp_radial(r, rs, vs);
+            let u = match kind {
+                RadialKind::R => r * v,
+                RadialKind::Chi => v,
+            };
+            match form {
+                RadialForm::U => u,
+                RadialForm::P => u * u,
+                RadialForm::R => match kind {
+                    RadialKind::R => v,
+                    RadialKind::Chi if r > 0.0 => v / r,
+                    RadialKind::Chi => f32::NAN,
+                },
+            }
+        })
+        .collect();
+    // u / r is undefined at the origin; carry the nearest finite value.
+    if out.len() > 1 && out[0].is_nan() {
+        out[0] = out[1];
+    }
+    out
+}
+
+fn image_dims(q: &ImageQuery) -> (u32, u32) {
+    let width = q.width.unwrap_or(256).clamp(16, 1024);
+    let height = q.height.unwrap_or(width).clamp(16, 1024);
+    (width, height)
+}
+
+// Row-major with +z on the top row.
+fn slice_grid(
+    qn: QuantumNumbers,
+    z: u32,
+    extent: f32,
+    width: u32,
+    height: u32,
+    basis: AngularBasis,
+) -> Vec<f32> {
+    let z_f = z as f32;
+    let mut grid = Vec::with_capacity((width * height) as usize);
+    for row in 0..height {
+        let pz = extent * (1.0 - 2.0 * (row as f32 + 0.5) / height as f32);
+        for col in 0..width {
+            let px = extent * (2.0 * (col as f32 + 0.5) / width as f32 - 1.0);
+            let r = (px * px + pz * pz).sqrt();
+            let theta = if r > 0.0 { (pz / r).clamp(-1.0, 1.0).acos() } else { 0.0 };
+            let phi = if px < 0.0 { std::f32::consts::PI } else { 0.0 };
+            let radial = radial_wavefunction(r * z_f, qn.n, qn.l);
+            let angular = angular_wavefunction_basis(theta, phi, qn.l, qn.m_l, basis);
+            let psi = radial * angular;
+            grid.push(psi * psi);
+        }
+    }
+    grid
+}
+
+// Equirectangular: phi across, theta down.
+fn angular_grid(l: u32, m_l: i32, width: u32, height: u32, basis: AngularBasis) -> Vec<f32> {
+    use std::f32::consts::PI;
+
+    let mut grid = Vec::with_capacity((width * height) as usize);
+    for row in 0..height {
+        let theta = PI * (row as f32 + 0.5) / height as f32;
+        for col in 0..width {
+            let phi = 2.0 * PI * (col as f32 + 0.5) / width as f32;
+            let y = angular_wavefunction_basis(theta, phi, l, m_l, basis);
+            grid.push(y * y);
+        }
+    }
+    grid
+}
+
+fn interp_stops(stops: &[(f32, [f32; 3])], t: f32) -> [f32; 3] {
+    for pair in stops.windows(2) {
+        let (t0, c0) = pair[0];
+        let (t1, c1) = pair[1];
+        if t <= t1 {
+            let k = ((t - t0) / (t1 - t0).max(1e-6)).clamp(0.0, 1.0);
+            return [
+                c0[0] + (c1[0] - c0[0]) * k,
+                c0[1] + (c1[1] - c0[1]) * k,
+                c0[2] + (c1[2] - c0[2]) * k,
+            ];
+        }
+    }
+    stops.last().map(|s| s.1).unwrap_or([0.0; 3])
+}
+
+fn encode_heatmap_png(
+    grid: &[f32],
+    width: u32,
+    height: u32,
+    colormap: Colormap,
+) -> Result<Vec<u8>, String> {
+    let peak = grid.iter().copied().fold(0.0_f32, f32::max);
+    let scale = if peak > 0.0 { 1.0 / peak } else { 0.0 };
+    let pixels: Vec<u8> = grid.iter().flat_map(|v| colormap.color(v * scale)).collect();
+    let img = image::RgbImage::from_raw(width, height, pixels)
+        .ok_or_else(|| "grid does not match image dimensions".to_string())?;
+    let mut bytes = Vec::new();
+    img.write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageOutputFormat::Png)
+        .map_err(|e| format!("png encode failed: {e}"))?;
+    Ok(bytes)
+}
+
+fn png_response(grid: &[f32], width: u32, height: u32, colormap: Colormap) -> Response {
+    match encode_heatmap_png(grid, width, height, colormap) {
+        Ok(bytes) => ([(header::CONTENT_TYPE, "image/png")], bytes).into_response(),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err).into_response(),
+    }
 }
 
 fn lda_available_orbitals(data: &LdaElement) -> Vec<OrbitalInfo> {
@@ -3350,6 +4500,46 @@ fn lda_available_orbitals(data: &LdaElement) -> Vec<OrbitalInfo> {
     list
 }
 
+// Falls back to the largest (n, l) when the .alog has no eigenvalues.
+fn lda_default_orbital(data: &LdaElement) -> Option<OrbitalInfo> {
+    let energy = |orb: &LdaOrbital| {
+        data.eigenvalues
+            .get(&(orb.n, orb.l))
+            .copied()
+            .unwrap_or(f32::NEG_INFINITY)
+    };
+    let occupied = occupied_orbitals(data);
+    let use_energy = occupied.iter().any(|(orb, _)| energy(*orb).is_finite());
+    occupied
+        .into_iter()
+        .max_by(|(a, _), (b, _)| {
+            if use_energy {
+                energy(*a).partial_cmp(&energy(*b)).unwrap_or(Ordering::Equal)
+            } else {
+                (a.n, a.l).cmp(&(b.n, b.l))
+            }
+        })
+        .map(|(orb, _)| OrbitalInfo {
+            label: orb.label.clone(),
+            n: orb.n,
+            l: orb.l,
+        })
+}
+
+// Skips unoccupied projectors such as 4p in 3d metals.
+fn pslib_default_orbital(data: &ElementData) -> Option<OrbitalInfo> {
+    let has_occupations = data.orbitals.iter().any(|orb| orb.occupation.is_some());
+    data.orbitals
+        .iter()
+        .filter(|orb| !has_occupations || orb.occupation.unwrap_or(0.0) > 0.0)
+        .max_by(|a, b| (a.n, a.l).cmp(&(b.n, b.l)))
+        .map(|orb| OrbitalInfo {
+            label: orb.label.clone(),
+            n: orb.n,
+            l: orb.l,
+        })
+}
+
 fn occupied_orbitals(data: &LdaElement) -> Vec<(&LdaOrbital, f32)> {
     let mut list = Vec::new();
     for orb in &data.orbitals {
@@ -3490,15 +4680,21 @@ fn generate_orbital_samples_from_radial(
     max_radius: f32,
     radial_kind: RadialKind,
     basis: AngularBasis,
+    profile: &mut SamplingProfile,
 ) -> Vec<[f32; 3]> {
     use rand::Rng;
     use std::f32::consts::PI;
 
+    let start = Instant::now();
     let mut samples = Vec::with_capacity(num_samples);
     let mut rng = rand::thread_rng();
 
+    let cdf_start = Instant::now();
     let cdf = build_radial_cdf(radial_r, radial_val, max_radius, radial_kind);
-    let max_ang = max_angular_prob(l, m_l, basis);
+    let angular_start = Instant::now();
+    let isotropic = l == 0;
+    let max_ang = if isotropic { 0.0 } else { max_angular_prob(l, m_l, basis) };
+    let loop_start = Instant::now();
     let mut attempts = 0usize;
     let max_attempts = num_samples.saturating_mul(300).max(1000);
 
@@ -3507,33 +4703,52 @@ fn generate_orbital_samples_from_radial(
         let r = sample_r(&cdf, radial_r, &mut rng);
         let phi = rng.gen::<f32>() * 2.0 * PI;
 
-        // Rejection sample theta from |Y_lm|^2 with a bounded loop
-        let mut accepted = false;
-        for _ in 0..256 {
-            let cos_theta = rng.gen::<f32>() * 2.0 - 1.0;
-            let theta = cos_theta.acos();
-            let ang = angular_wavefunction_basis(theta, phi, l, m_l, basis);
-            if !ang.is_finite() {
-                continue;
-            }
-            let p = (ang * ang) / max_ang;
-            if rng.gen::<f32>() < p.min(1.0) {
-                let x = r * theta.sin() * phi.cos();
-                let y = r * theta.sin() * phi.sin();
-                let z = r * theta.cos();
-                samples.push([x, y, z]);
-                accepted = true;
-                break;
+        let theta = if isotropic {
+            sample_theta_isotropic(&mut rng)
+        } else {
+            match sample_theta_rejection(phi, l, m_l, basis, max_ang, &mut rng) {
+                Some(theta) => theta,
+                None => continue,
             }
-        }
-        if !accepted {
-            continue;
-        }
+        };
+        let x = r * theta.sin() * phi.cos();
+        let y = r * theta.sin() * phi.sin();
+        let z = r * theta.cos();
+        samples.push([x, y, z]);
     }
 
+    profile.record(start, cdf_start, angular_start, loop_start, attempts, samples.len());
     samples
 }
 
+// Y_00 is constant, so cos(theta) is uniform and no rejection is needed.
+fn sample_theta_isotropic<R: rand::Rng>(rng: &mut R) -> f32 {
+    (rng.gen::<f32>() * 2.0 - 1.0).acos()
+}
+
+fn sample_theta_rejection<R: rand::Rng>(
+    phi: f32,
+    l: u32,
+    m_l: i32,
+    basis: AngularBasis,
+    max_ang: f32,
+    rng: &mut R,
+) -> Option<f32> {
+    for _ in 0..256 {
+        let cos_theta = rng.gen::<f32>() * 2.0 - 1.0;
+        let theta = cos_theta.acos();
+        let ang = angular_wavefunction_basis(theta, phi, l, m_l, basis);
+        if !ang.is_finite() {
+            continue;
+        }
+        let p = (ang * ang) / max_ang;
+        if rng.gen::<f32>() < p.min(1.0) {
+            return Some(theta);
+        }
+    }
+    None
+}
+
 fn generate_superposition_samples_lda(
     orb_a: &LdaOrbital,
     orb_b: &LdaOrbital,
@@ -3546,10 +4761,12 @@ fn generate_superposition_samples_lda(
     delta_e: f32,
     with_psi: bool,
     basis: AngularBasis,
+    profile: &mut SamplingProfile,
 ) -> (Vec<[f32; 3]>, Vec<[f32; 2]>, Vec<[f32; 2]>) {
     use rand::Rng;
     use std::f32::consts::PI;
 
+    let start = Instant::now();
     let mut rng = rand::thread_rng();
     let mut samples = Vec::with_capacity(num_samples);
     let mut psi1 = Vec::new();
@@ -3564,11 +4781,15 @@ fn generate_superposition_samples_lda(
     let phase_re = (delta_e * time).cos();
     let phase_im = -(delta_e * time).sin();
 
+    let cdf_start = Instant::now();
     let cdf_a = build_radial_cdf(&orb_a.radial_r, &orb_a.radial_rfn, max_radius, RadialKind::R);
     let cdf_b = build_radial_cdf(&orb_b.radial_r, &orb_b.radial_rfn, max_radius, RadialKind::R);
+    let angular_start = Instant::now();
     let max_ang_a = max_angular_prob(orb_a.l, m_a, basis);
     let max_ang_b = max_angular_prob(orb_b.l, m_b, basis);
+    let loop_start = Instant::now();
     if cdf_a.is_empty() || cdf_b.is_empty() {
+        profile.record(start, cdf_start, angular_start, loop_start, 0, 0);
         return (samples, psi1, psi2);
     }
 
@@ -3647,6 +4868,7 @@ fn generate_superposition_samples_lda(
         }
     }
 
+    profile.record(start, cdf_start, angular_start, loop_start, attempts, samples.len());
     (samples, psi1, psi2)
 }
 
@@ -3660,10 +4882,12 @@ fn generate_superposition_samples_hydrogenic(
     delta_e: f32,
     with_psi: bool,
     basis: AngularBasis,
+    profile: &mut SamplingProfile,
 ) -> (Vec<[f32; 3]>, Vec<[f32; 2]>, Vec<[f32; 2]>) {
     use rand::Rng;
     use std::f32::consts::PI;
 
+    let start = Instant::now();
     let mut rng = rand::thread_rng();
     let mut samples = Vec::with_capacity(num_samples);
     let mut psi1 = Vec::new();
@@ -3677,6 +4901,7 @@ fn generate_superposition_samples_hydrogenic(
     let phase_re = (delta_e * time).cos();
     let phase_im = -(delta_e * time).sin();
 
+    let cdf_start = Instant::now();
     let radial_steps = 800usize;
     let rs = build_radial_grid(max_radius, radial_steps);
     let rfn_a: Vec<f32> = rs
@@ -3689,9 +4914,12 @@ fn generate_superposition_samples_hydrogenic(
         .collect();
     let cdf_a = build_radial_cdf(&rs, &rfn_a, max_radius, RadialKind::R);
     let cdf_b = build_radial_cdf(&rs, &rfn_b, max_radius, RadialKind::R);
+    let angular_start = Instant::now();
     let max_ang_a = max_angular_prob(qn_a.l, qn_a.m_l, basis);
     let max_ang_b = max_angular_prob(qn_b.l, qn_b.m_l, basis);
+    let loop_start = Instant::now();
     if cdf_a.is_empty() || cdf_b.is_empty() {
+        profile.record(start, cdf_start, angular_start, loop_start, 0, 0);
         return (samples, psi1, psi2);
     }
 
@@ -3769,9 +4997,84 @@ fn generate_superposition_samples_hydrogenic(
         }
     }
 
+    profile.record(start, cdf_start, angular_start, loop_start, attempts, samples.len());
     (samples, psi1, psi2)
 }
 
+// Everything except `t` that shapes a skeleton.
+fn skeleton_key(
+    source: &str,
+    z: u32,
+    q: &SampleQuery,
+    mix: f32,
+    count: usize,
+    max_radius: f32,
+    basis: AngularBasis,
+) -> String {
+    format!(
+        "{source}|{z}|{:?},{:?},{:?}|{:?},{:?},{:?}|{mix}|{count}|{max_radius}|{basis:?}",
+        q.n, q.l, q.m, q.n2, q.l2, q.m2
+    )
+}
+
+fn reuse_skeleton(token: &str, key: &str) -> Option<Arc<Skeleton>> {
+    let mut sessions = SKELETON_SESSIONS.write().ok()?;
+    let session = sessions.get_mut(token)?;
+    if session.key != key || session.touched.elapsed() > SKELETON_TTL {
+        return None;
+    }
+    session.touched = Instant::now();
+    Some(Arc::clone(&session.skeleton))
+}
+
+fn store_skeleton(key: String, skeleton: Arc<Skeleton>) -> Option<String> {
+    use rand::Rng;
+
+    let size = skeleton.0.len();
+    if size > MAX_SKELETON_SAMPLES {
+        return None;
+    }
+    let token = format!("{:016x}", rand::thread_rng().gen::<u64>());
+    let mut sessions = SKELETON_SESSIONS.write().ok()?;
+    sessions.retain(|_, s| s.touched.elapsed() <= SKELETON_TTL);
+    let mut stored: usize = sessions.values().map(|s| s.skeleton.0.len()).sum();
+    while sessions.len() >= MAX_SKELETON_SESSIONS || stored + size > MAX_SKELETON_SAMPLES {
+        let oldest = sessions
+            .iter()
+            .min_by_key(|(_, s)| s.touched)
+            .map(|(k, _)| k.clone());
+        match oldest.and_then(|k| sessions.remove(&k)) {
+            Some(evicted) => stored -= evicted.skeleton.0.len(),
+            None => break,
+        }
+    }
+    sessions.insert(
+        token.clone(),
+        SkeletonSession {
+            key,
+            skeleton,
+            touched: Instant::now(),
+        },
+    );
+    Some(token)
+}
+
+// Callers need owned vectors for the response; only copy when the cache shares them.
+fn take_skeleton(skeleton: Arc<Skeleton>) -> Skeleton {
+    Arc::try_unwrap(skeleton).unwrap_or_else(|shared| (*shared).clone())
+}
+
+fn empirical_mean_radius(samples: &[[f32; 3]]) -> Option<f32> {
+    if samples.is_empty() {
+        return None;
+    }
+    let sum: f64 = samples
+        .iter()
+        .map(|p| ((p[0] * p[0] + p[1] * p[1] + p[2] * p[2]) as f64).sqrt())
+        .sum();
+    Some((sum / samples.len() as f64) as f32)
+}
+
 fn build_radial_grid(max_radius: f32, steps: usize) -> Vec<f32> {
     let count = steps.max(2);
     let mut rs = Vec::with_capacity(count);
@@ -3808,6 +5111,66 @@ fn interp_radial(r: f32, rs: &[f32], vs: &[f32]) -> f32 {
     v0 + (v1 - v0) * t
 }
 
+// Near-zero values are skipped so tail noise and the r = 0 zero of l > 0
+// functions don't count as nodes.
+fn radial_nodes(rs: &[f32], vs: &[f32]) -> Vec<f32> {
+    let peak = vs.iter().fold(0.0_f32, |acc, v| acc.max(v.abs()));
+    let floor = peak * 1e-4;
+    let mut nodes = Vec::new();
+    let mut last: Option<(f32, f32)> = None;
+    for (&r, &v) in rs.iter().zip(vs) {
+        if v.abs() <= floor {
+            continue;
+        }
+        if let Some((r0, v0)) = last {
+            if (v0 > 0.0) != (v > 0.0) {
+                nodes.push(r0 + (r - r0) * v0 / (v0 - v));
+            }
+        }
+        last = Some((r, v));
+    }
+    nodes
+}
+
+fn hydrogenic_radial_nodes(n: u32, l: u32, max_radius: f32) -> Vec<f32> {
+    let rs = build_radial_grid(max_radius, 4096);
+    let vs: Vec<f32> = rs.iter().map(|&r| radial_wavefunction(r, n, l)).collect();
+    radial_nodes(&rs, &vs)
+}
+
+fn shell_indices(samples: &[[f32; 3]], nodes: &[f32]) -> Vec<u8> {
+    samples
+        .iter()
+        .map(|p| {
+            let r = (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt();
+            nodes.partition_point(|&node| node < r).min(u8::MAX as usize) as u8
+        })
+        .collect()
+}
+
+// Roughly one pixel when [-max, max] spans a 1000 px wide view. With the default
+// max = 20 only 1s clouds past Z = 37 fall below it.
+const MIN_VISIBLE_FRACTION: f32 = 0.002;
+
+// <r> = (3n^2 - l(l+1)) / 2 in Bohr radii.
+fn hydrogenic_mean_radius(n: u32, l: u32) -> f32 {
+    (3.0 * (n * n) as f32 - (l * (l + 1)) as f32) / 2.0
+}
+
+// Below resolution the samples stay in a0/Z instead of being shrunk by 1/Z.
+fn hydrogenic_display_scale(z: u32, mean_radius: f32, max_radius: f32) -> (f32, Option<String>) {
+    let inv_z = 1.0 / z.max(1) as f32;
+    let physical = mean_radius * inv_z;
+    if z > 1 && physical < MIN_VISIBLE_FRACTION * max_radius {
+        let note = format!(
+            "Z={z} cloud is sub-resolution (<r> = {physical:.3} a0); lengths shown in a0/Z, hydrogenic approximation"
+        );
+        (1.0, Some(note))
+    } else {
+        (inv_z, None)
+    }
+}
+
 fn hydrogenic_energy(n: u32) -> f32 {
     let n_f = n as f32;
     -0.5 / (n_f * n_f)
@@ -3911,6 +5274,7 @@ fn generate_weighted_orbital_samples(
             max_radius,
             radial_kind,
             basis,
+            &mut SamplingProfile::default(),
         );
         samples.append(&mut part);
     }
@@ -4423,6 +5787,10 @@ fn sample_r<R: rand::Rng>(cdf: &[f32], rs: &[f32], rng: &mut R) -> f32 {
 
 fn max_angular_prob(l: u32, m_l: i32, basis: AngularBasis) -> f32 {
     use std::f32::consts::PI;
+    if l == 0 {
+        // |Y_00|^2 = 1 / (4 pi) everywhere; no scan needed.
+        return 1.0 / (4.0 * PI);
+    }
     let mut max_val = 0.0_f32;
     let theta_steps = 180;
     let phi_steps = if matches!(basis, AngularBasis::Complex) { 1 } else { 72 };
@@ -4446,6 +5814,9 @@ async fn main() {
         .route("/", get(index))
         .route("/info", get(info))
         .route("/samples", get(samples))
+        .route("/radial", get(radial))
+        .route("/slice.png", get(slice_png))
+        .route("/angular.png", get(angular_png))
         .route("/static/three.module.js", get(three_module))
         .route("/static/MarchingCubes.js", get(marching_cubes));
     let port: u16 = std::env::var("PORT")
@@ -4458,4 +5829,422 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lda_element(symbol: &str, shells: &[(u32, u32, f32, f32)]) -> LdaElement {
+        let mut occupancy = HashMap::new();
+        let mut eigenvalues = HashMap::new();
+        let mut orbitals = Vec::new();
+        for &(n, l, occ, eig) in shells {
+            if occ > 0.0 {
+                occupancy.insert((n, l), occ);
+            }
+            eigenvalues.insert((n, l), eig);
+            orbitals.push(LdaOrbital {
+                n,
+                l,
+                label: format!("{n}{}", ["s", "p", "d", "f"][l as usize]),
+                radial_r: vec![0.0, 1.0],
+                radial_rfn: vec![0.0, 0.0],
+            });
+        }
+        let total: f32 = shells.iter().map(|s| s.2).sum();
+        LdaElement {
+            symbol: symbol.to_string(),
+            orbitals,
+            occupancy,
+            eigenvalues,
+            total_electrons: total,
+            valence_electrons: total,
+            r_max: 1.0,
+            data_file: format!("{symbol}.alog"),
+        }
+    }
+
+    #[test]
+    fn test_index_embeds_default_render_mode() {
+        let page = render_index_html(None, None);
+        assert!(page.contains(r#""renderMode":"dots""#));
+        assert!(!page.contains("__SERVER_DEFAULTS__"));
 
+        let page = render_index_html(Some(" Bubbles "), Some("phase"));
+        assert!(page.contains(r#""renderMode":"bubbles""#));
+        assert!(page.contains(r#""dotColorMode":"phase""#));
+
+        let page = render_index_html(Some("wireframe"), None);
+        assert!(page.contains(r#""renderMode":"dots""#));
+    }
+
+    fn cos_theta_histogram(thetas: &[f32], bins: usize) -> Vec<f32> {
+        let mut hist = vec![0.0_f32; bins];
+        for theta in thetas {
+            let u = (theta.cos() + 1.0) * 0.5;
+            let idx = ((u * bins as f32) as usize).min(bins - 1);
+            hist[idx] += 1.0;
+        }
+        hist.iter().map(|c| c / thetas.len() as f32).collect()
+    }
+
+    #[test]
+    fn test_s_orbital_fast_path_is_isotropic() {
+        let mut rng = rand::thread_rng();
+        let draws = 200_000;
+        let max_ang = max_angular_prob(0, 0, AngularBasis::Complex);
+        let fast: Vec<f32> = (0..draws).map(|_| sample_theta_isotropic(&mut rng)).collect();
+        let general: Vec<f32> = (0..draws)
+            .filter_map(|_| sample_theta_rejection(0.0, 0, 0, AngularBasis::Complex, max_ang, &mut rng))
+            .collect();
+        assert!(general.len() > draws * 99 / 100);
+
+        let bins = 10;
+        let fast_hist = cos_theta_histogram(&fast, bins);
+        let general_hist = cos_theta_histogram(&general, bins);
+        for (f, g) in fast_hist.iter().zip(&general_hist) {
+            assert!((f - 0.1).abs() < 0.005, "fast path bin {f}");
+            assert!((f - g).abs() < 0.007, "fast {f} vs general {g}");
+        }
+
+        // End-to-end: a 1s cloud drawn through the fast path has <z^2> = <r^2> / 3.
+        let rs = build_radial_grid(20.0, 800);
+        let rfn: Vec<f32> = rs.iter().map(|r| radial_wavefunction(*r, 1, 0)).collect();
+        let samples = generate_orbital_samples_from_radial(
+            &rs,
+            &rfn,
+            0,
+            0,
+            50_000,
+            20.0,
+            RadialKind::R,
+            AngularBasis::Real,
+            &mut SamplingProfile::default(),
+        );
+        assert_eq!(samples.len(), 50_000);
+        let (mut z2, mut r2) = (0.0_f64, 0.0_f64);
+        for p in &samples {
+            z2 += (p[2] * p[2]) as f64;
+            r2 += (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]) as f64;
+        }
+        let ratio = z2 / r2;
+        assert!((ratio - 1.0 / 3.0).abs() < 0.02, "<z^2>/<r^2> = {ratio}");
+    }
+
+    async fn sample_json(query: serde_json::Value) -> serde_json::Value {
+        let q: SampleQuery = serde_json::from_value(query).unwrap();
+        let body = samples(Query(q)).await.into_response().into_body();
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_high_z_hydrogenic_1s_stays_visible() {
+        let mean_1s = hydrogenic_mean_radius(1, 0);
+        for z in [2, 4, 10, 37] {
+            let (scale, note) = hydrogenic_display_scale(z, mean_1s, 20.0);
+            assert_eq!(scale, 1.0 / z as f32, "Z={z}");
+            assert!(note.is_none(), "Z={z} flagged: {note:?}");
+        }
+
+        let qn = QuantumNumbers::new(1, 0, 0).unwrap();
+        let raw = generate_orbital_samples(qn, 5000, 20.0);
+        let out = hydrogenic_orbital_response(qn, 100, 5000, 20.0, raw.clone(), None);
+        assert!(!out.samples.is_empty());
+        assert!(out.samples.iter().flatten().all(|v| v.is_finite()));
+        let mean_r = empirical_mean_radius(&out.samples).unwrap();
+        assert!(mean_r > 1.0, "cloud collapsed to <r> = {mean_r}");
+        assert_eq!(out.max_radius, 20.0);
+        assert_eq!(out.length_unit.as_deref(), Some("a0/Z"));
+        let note = out.note.unwrap_or_default();
+        assert!(note.contains("sub-resolution"), "{note}");
+
+        let out = hydrogenic_orbital_response(qn, 10, 5000, 20.0, raw, None);
+        assert!((out.max_radius - 2.0).abs() < 1e-6);
+        assert!(out.length_unit.is_none());
+        assert!(out.note.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_profile_components_sum_to_total() {
+        let json = sample_json(serde_json::json!({
+            "mode": "superposition", "z": 1, "n": 2, "l": 1, "m": 0,
+            "n2": 3, "l2": 2, "m2": 0, "count": 20000, "profile": true,
+        }))
+        .await;
+        let profile = &json["profile"];
+        assert!(profile.is_object(), "profile missing: {json}");
+        let field = |key: &str| profile[key].as_f64().unwrap();
+        let parts = field("radial_cdf_ms") + field("angular_max_ms") + field("rejection_ms");
+        let total = field("total_ms");
+        assert!(total > 0.0);
+        assert!(parts <= total + 1e-3, "parts {parts} > total {total}");
+        assert!(total - parts < 0.1 * total + 0.5, "parts {parts} vs total {total}");
+
+        let accepted = profile["accepted"].as_u64().unwrap();
+        let attempts = profile["attempts"].as_u64().unwrap();
+        assert_eq!(accepted as usize, json["samples"].as_array().unwrap().len());
+        assert!(attempts >= accepted);
+        let ratio = field("acceptance_ratio");
+        assert!(ratio > 0.0 && ratio <= 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_superposition_session_reuses_skeleton() {
+        let base = serde_json::json!({
+            "mode": "superposition", "z": 1, "n": 2, "l": 1, "m": 0,
+            "n2": 3, "l2": 2, "m2": 0, "count": 1000, "animated": true,
+            "color_mode": "phase", "session": "", "t": 0.0,
+        });
+        let first = sample_json(base.clone()).await;
+        let token = first["session"].as_str().expect("session token").to_string();
+
+        let mut follow_up = base.clone();
+        follow_up["session"] = serde_json::Value::String(token.clone());
+        follow_up["t"] = serde_json::json!(1.5);
+        let second = sample_json(follow_up).await;
+
+        assert_eq!(second["session"].as_str(), Some(token.as_str()));
+        assert_eq!(first["samples"], second["samples"]);
+        assert_ne!(first["phases"], second["phases"]);
+    }
+
+    #[test]
+    fn test_oversized_skeleton_is_not_cached() {
+        let skeleton = (vec![[0.0_f32; 3]; MAX_SKELETON_SAMPLES + 1], Vec::new(), Vec::new());
+        assert!(store_skeleton("oversized".to_string(), Arc::new(skeleton)).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reduced_radial_1s_peaks_at_bohr_radius() {
+        let q: RadialQuery = serde_json::from_value(serde_json::json!({
+            "n": 1, "l": 0, "z": 1, "max": 10.0, "points": 1001, "radial_form": "u",
+        }))
+        .unwrap();
+        let body = radial(Query(q)).await.into_body();
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["radial_form"], "u");
+
+        let rs: Vec<f32> = serde_json::from_value(json["r"].clone()).unwrap();
+        let us: Vec<f32> = serde_json::from_value(json["values"].clone()).unwrap();
+        assert_eq!(rs.len(), us.len());
+        for (&r, &u) in rs.iter().zip(&us) {
+            assert!((u - r * radial_wavefunction(r, 1, 0)).abs() < 1e-5, "r={r}");
+        }
+        let (peak, _) = rs
+            .iter()
+            .zip(&us)
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .unwrap();
+        assert!((peak - 1.0).abs() < 0.011, "u(r) peaks at {peak}");
+    }
+
+    #[tokio::test]
+    async fn test_real_dz2_has_two_lobes_and_ring() {
+        let json = sample_json(serde_json::json!({
+            "mode": "orbital", "z": 1, "n": 3, "l": 2, "m": 0,
+            "basis": "real", "count": 40000, "max": 25.0,
+        }))
+        .await;
+        assert_eq!(json["m"], 0);
+        let samples: Vec<[f32; 3]> = serde_json::from_value(json["samples"].clone()).unwrap();
+        // About 1 in 50 uniform proposals is accepted at max = 25, well inside the 100x cap.
+        assert_eq!(samples.len(), 40_000);
+
+        // 3cos^2(theta) - 1 has nodal cones at |cos(theta)| = 1/sqrt(3).
+        let cone = 1.0 / 3.0_f32.sqrt();
+        let (mut up, mut down, mut ring, mut near_cone) = (0usize, 0usize, 0usize, 0usize);
+        for p in &samples {
+            let r = (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt().max(1e-6);
+            let cos_theta = p[2] / r;
+            if cos_theta > cone {
+                up += 1;
+            } else if cos_theta < -cone {
+                down += 1;
+            } else {
+                ring += 1;
+            }
+            if (cos_theta.abs() - cone).abs() < 0.02 {
+                near_cone += 1;
+            }
+        }
+        let total = samples.len() as f32;
+        // Integrating (3c^2 - 1)^2 over c puts 61.5% of the density in the lobes.
+        assert!(((up + down) as f32 / total - 0.615).abs() < 0.02);
+        assert!((up as f32 / down as f32 - 1.0).abs() < 0.1);
+        assert!(ring as f32 / total > 0.35);
+        assert!((near_cone as f32 / total) < 0.01, "density on the nodal cones");
+    }
+
+    #[tokio::test]
+    async fn test_slice_png_2pz_has_dark_nodal_line() {
+        let q: ImageQuery = serde_json::from_value(serde_json::json!({
+            "n": 2, "l": 1, "m": 0, "width": 160, "height": 128, "max": 12.0,
+        }))
+        .unwrap();
+        let response = slice_png(Query(q)).await;
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "image/png"
+        );
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let img = image::load_from_memory_with_format(&bytes, image::ImageFormat::Png)
+            .unwrap()
+            .to_rgb8();
+        assert_eq!(img.dimensions(), (160, 128));
+
+        let brightness =
+            |x: u32, y: u32| img.get_pixel(x, y).0.iter().map(|&c| c as u32).sum::<u32>();
+        let peak = (0..128)
+            .flat_map(|y| (0..160).map(move |x| (x, y)))
+            .map(|(x, y)| brightness(x, y))
+            .max()
+            .unwrap();
+        // The xy plane (z = 0) runs across the middle two rows of the xz slice.
+        for y in [63, 64] {
+            let row_peak = (0..160).map(|x| brightness(x, y)).max().unwrap();
+            assert!(row_peak * 3 < peak, "row {y}: {row_peak} vs peak {peak}");
+        }
+    }
+
+    #[test]
+    fn test_3s_shell_indices_split_at_radial_nodes() {
+        let nodes = hydrogenic_radial_nodes(3, 0, 40.0);
+        // R_30 vanishes at r = (9 -+ 3*sqrt(3)) / 2.
+        let expected = [(9.0 - 3.0 * 3.0_f32.sqrt()) / 2.0, (9.0 + 3.0 * 3.0_f32.sqrt()) / 2.0];
+        assert_eq!(nodes.len(), 2);
+        for (node, want) in nodes.iter().zip(expected) {
+            assert!((node - want).abs() < 0.02, "node {node} vs {want}");
+        }
+
+        // The CDF sampler always returns the full count; the inner shell holds
+        // about 1.4% of the probability, so shell 0 gets a few hundred points.
+        let rs = build_radial_grid(30.0, 2000);
+        let rfn: Vec<f32> = rs.iter().map(|r| radial_wavefunction(*r, 3, 0)).collect();
+        let samples = generate_orbital_samples_from_radial(
+            &rs,
+            &rfn,
+            0,
+            0,
+            20_000,
+            30.0,
+            RadialKind::R,
+            AngularBasis::Real,
+            &mut SamplingProfile::default(),
+        );
+        assert_eq!(samples.len(), 20_000);
+        let shells = shell_indices(&samples, &nodes);
+        let mut counts = [0usize; 3];
+        for &shell in &shells {
+            counts[shell as usize] += 1;
+        }
+        let inner = counts[0] as f32 / samples.len() as f32;
+        assert!(inner > 0.008 && inner < 0.022, "inner shell fraction {inner}");
+        assert!(counts[1] > 0 && counts[2] > counts[1], "{counts:?}");
+        for (p, shell) in samples.iter().zip(&shells) {
+            let r = (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt();
+            let want = expected.iter().filter(|&&node| node < r).count() as u8;
+            if (r - expected[0]).abs() > 0.05 && (r - expected[1]).abs() > 0.05 {
+                assert_eq!(*shell, want, "r={r}");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_empirical_mean_radius_matches_analytic_2p() {
+        // max = 15 keeps the rejection sampler's acceptance high enough to fill
+        // the requested count while cutting off well under 0.1% of the density.
+        let json = sample_json(serde_json::json!({
+            "mode": "orbital", "z": 1, "n": 2, "l": 1, "m": 0, "count": 20000,
+            "max": 15.0, "mean_radius": true,
+        }))
+        .await;
+        let samples = json["samples"].as_array().expect("samples");
+        assert!(samples.len() >= 19_000, "only {} samples", samples.len());
+        let mean = json["empirical_mean_radius"].as_f64().expect("empirical_mean_radius") as f32;
+        // <r>_nl = (3n^2 - l(l+1)) / 2 in Bohr radii, so 5 for 2p.
+        let analytic = 5.0;
+        assert!(
+            ((mean - analytic) / analytic).abs() < 0.03,
+            "empirical <r> = {mean}, analytic = {analytic}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_provenance_reports_parsed_alog_file() {
+        let dir = std::env::temp_dir().join(format!("atoms-provenance-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Li_test.alog");
+        let mut alog = String::from("total.electron 3.0\nvalence.electron 3.0\n");
+        alog.push_str("<ocupied.electrons\n 1 2.0\n 2 1.0\nocupied.electrons>\n");
+        alog.push_str("Radial wave functions\n n= 1\n");
+        for i in 0..400 {
+            let r = i as f32 * 0.05;
+            alog.push_str(&format!("{i} {r} {}\n", 2.0 * (-r).exp()));
+        }
+        alog.push_str("Charge density\n");
+        std::fs::write(&path, alog).unwrap();
+
+        let element = atomic_lda::parse_alog(&path, "Li").unwrap();
+        assert_eq!(element.data_file, path.display().to_string());
+        atomic_lda::cache_element(element);
+
+        let json = sample_json(serde_json::json!({
+            "mode": "orbital", "z": 3, "n": 1, "l": 0, "count": 1000, "provenance": true,
+        }))
+        .await;
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(json["source"], "openmx_lda");
+        assert_eq!(json["selected_orbital"], "1s");
+        let data_file = json["data_file"].as_str().expect("data_file");
+        assert!(data_file.ends_with("Li_test.alog"), "{data_file}");
+    }
+
+    #[test]
+    fn test_lda_default_orbital_is_max_eigenvalue_over_occupied_shells() {
+        let carbon = lda_element(
+            "C",
+            &[(1, 0, 2.0, -9.95), (2, 0, 2.0, -0.50), (2, 1, 2.0, -0.20), (3, 0, 0.0, -0.05)],
+        );
+        let neon = lda_element(
+            "Ne",
+            &[(1, 0, 2.0, -30.3), (2, 0, 2.0, -1.32), (2, 1, 6.0, -0.50)],
+        );
+
+        let c = lda_default_orbital(&carbon).expect("carbon default");
+        assert_eq!((c.n, c.l, c.label.as_str()), (2, 1, "2p"));
+        let ne = lda_default_orbital(&neon).expect("neon default");
+        assert_eq!((ne.n, ne.l, ne.label.as_str()), (2, 1, "2p"));
+    }
+
+    #[test]
+    fn test_pslib_default_orbital_skips_unoccupied_projectors() {
+        let orbital = |label: &str, n: u32, l: u32, occupation: Option<f32>| Orbital {
+            n,
+            l,
+            label: label.to_string(),
+            occupation,
+            radial_r: vec![0.0, 1.0],
+            radial_chi: vec![0.0, 0.0],
+        };
+        let mut iron = ElementData {
+            symbol: "Fe".to_string(),
+            orbitals: vec![
+                orbital("3S", 3, 0, Some(2.0)),
+                orbital("4S", 4, 0, Some(2.0)),
+                orbital("3D", 3, 2, Some(6.0)),
+                orbital("4P", 4, 1, Some(0.0)),
+            ],
+            r_max: 1.0,
+            data_file: String::new(),
+        };
+        let fe = pslib_default_orbital(&iron).expect("iron default");
+        assert_eq!((fe.n, fe.l), (4, 0));
+
+        for orb in &mut iron.orbitals {
+            orb.occupation = None;
+        }
+        let fe = pslib_default_orbital(&iron).expect("iron default without occupations");
+        assert_eq!((fe.n, fe.l), (4, 1));
+    }
+}
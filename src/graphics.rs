@@ -72,6 +72,7 @@ pub struct Graphics {
     pub instance_buffer: wgpu::Buffer,
     pub vertex_count: u32,
     pub vertex_capacity: usize,
+    pub minimized: bool,
 }
 
 impl Graphics {
@@ -129,8 +130,9 @@ impl Graphics {
         let config = wgpu::SurfaceConfiguration {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
             format: surface_format,
-            width: size.width,
-            height: size.height,
+            // wgpu rejects zero-sized surfaces; the real size arrives with the next resize.
+            width: size.width.max(1),
+            height: size.height.max(1),
             present_mode: wgpu::PresentMode::Fifo,
             alpha_mode: wgpu::CompositeAlphaMode::Auto,
             view_formats: vec![],
@@ -254,6 +256,7 @@ impl Graphics {
             instance_buffer,
             vertex_count: 0,
             vertex_capacity,
+            minimized: size.width == 0 || size.height == 0,
         }
     }
 
@@ -279,6 +282,9 @@ impl Graphics {
     }
 
     pub fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        if self.minimized {
+            return Ok(());
+        }
         let output = self.surface.get_current_texture()?;
         let view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
 
@@ -321,13 +327,17 @@ impl Graphics {
     }
 
     pub fn resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>) {
-        if new_size.width > 0 && new_size.height > 0 {
-            self.size = new_size;
-            self.config.width = new_size.width;
-            self.config.height = new_size.height;
-            self.surface.configure(&self.device, &self.config);
-            self.update_camera();
+        if new_size.width == 0 || new_size.height == 0 {
+            // Keep the last valid configuration; reconfigure on the next nonzero resize.
+            self.minimized = true;
+            return;
         }
+        self.minimized = false;
+        self.size = new_size;
+        self.config.width = new_size.width;
+        self.config.height = new_size.height;
+        self.surface.configure(&self.device, &self.config);
+        self.update_camera();
     }
 
     fn update_camera(&self) {
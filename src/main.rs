@@ -141,6 +141,23 @@ async fn main() {
                         WindowEvent::Resized(physical_size) => {
                             graphics.resize(*physical_size);
                         }
+                        WindowEvent::RedrawRequested => {
+                            // Nothing to draw into while minimized (zero-sized surface).
+                            if graphics.minimized {
+                                return;
+                            }
+                            let vertices = app_state.generate_vertices();
+                            graphics.update_vertices(&vertices);
+
+                            match graphics.render() {
+                                Ok(()) => {}
+                                Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+                                    // Surface went stale (e.g. restored from minimize); reconfigure.
+                                    graphics.resize(window.inner_size());
+                                }
+                                Err(e) => eprintln!("Render error: {:?}", e),
+                            }
+                        }
                         WindowEvent::KeyboardInput {
                             event,
                             ..
@@ -232,23 +249,12 @@ async fn main() {
                 }
                 Event::AboutToWait => {
                     let now = std::time::Instant::now();
-                    if now.duration_since(last_render).as_millis() > 16 {
+                    if !graphics.minimized && now.duration_since(last_render).as_millis() > 16 {
                         // 60 FPS
                         window.request_redraw();
                         last_render = now;
                     }
                 }
-                Event::WindowEvent {
-                    event: WindowEvent::RedrawRequested,
-                    window_id,
-                } if window_id == window.id() => {
-                    let vertices = app_state.generate_vertices();
-                    graphics.update_vertices(&vertices);
-
-                    if let Err(e) = graphics.render() {
-                        eprintln!("Render error: {:?}", e);
-                    }
-                }
                 _ => {}
             }
         })
@@ -91,10 +91,11 @@ pub fn spherical_harmonic(theta: f32, phi: f32, l: u32, m_l: i32) -> (f32, f32)
     let base_re = norm * legendre * c;
     let base_im = norm * legendre * s;
 
+    // associated_legendre already carries the (-1)^m Condon-Shortley factor.
     if m_l >= 0 {
-        let cs = if m_abs % 2 == 0 { 1.0 } else { -1.0 };
-        (cs * base_re, cs * base_im)
+        (base_re, base_im)
     } else {
+        // Y_{l,-m} = (-1)^m conj(Y_{l,m})
         let sign = if m_abs % 2 == 0 { 1.0 } else { -1.0 };
         (sign * base_re, -sign * base_im)
     }
@@ -102,13 +103,15 @@ pub fn spherical_harmonic(theta: f32, phi: f32, l: u32, m_l: i32) -> (f32, f32)
 
 /// Real-valued spherical harmonic basis used for chemistry-style orbitals.
 /// m > 0 -> cos-like (Re), m < 0 -> sin-like (Im), m = 0 -> Y_l0
+/// The Condon-Shortley phase is undone so p_x, d_xz, ... stay positive along +x.
 pub fn real_spherical_harmonic(theta: f32, phi: f32, l: u32, m_l: i32) -> f32 {
     if m_l == 0 {
         return spherical_harmonic(theta, phi, l, 0).0;
     }
     let m_abs = m_l.abs();
     let (re, im) = spherical_harmonic(theta, phi, l, m_abs as i32);
-    let scale = 2.0_f32.sqrt();
+    let cs = if m_abs % 2 == 0 { 1.0 } else { -1.0 };
+    let scale = cs * 2.0_f32.sqrt();
     if m_l > 0 {
         scale * re
     } else {
@@ -438,4 +441,78 @@ mod tests {
         assert!(psi > 0.0);
         assert!(!psi.is_nan());
     }
+
+    /// Count sign changes in a sequence, ignoring values too close to zero to carry a sign.
+    fn sign_changes(values: &[f32], closed: bool) -> usize {
+        let signs: Vec<bool> = values
+            .iter()
+            .filter(|v| v.abs() > 1e-5)
+            .map(|v| *v > 0.0)
+            .collect();
+        let mut changes = signs.windows(2).filter(|w| w[0] != w[1]).count();
+        if closed && signs.len() > 1 && signs[0] != signs[signs.len() - 1] {
+            changes += 1;
+        }
+        changes
+    }
+
+    #[test]
+    fn test_angular_nodes_equal_l() {
+        let steps = 3600;
+        for l in 0..=3u32 {
+            for m in -(l as i32)..=(l as i32) {
+                // Polar scan off any vertical nodal plane: counts the l - |m| cones/planes in theta.
+                let polar: Vec<f32> = (0..steps)
+                    .map(|i| {
+                        let theta = (i as f32 + 0.5) / steps as f32 * PI;
+                        real_spherical_harmonic(theta, 0.1, l, m)
+                    })
+                    .collect();
+                // Azimuthal circle away from any theta node: each vertical plane is crossed twice.
+                let azimuthal: Vec<f32> = (0..steps)
+                    .map(|i| {
+                        let phi = i as f32 / steps as f32 * 2.0 * PI;
+                        real_spherical_harmonic(0.37, phi, l, m)
+                    })
+                    .collect();
+
+                let polar_nodes = sign_changes(&polar, false);
+                let azimuthal_crossings = sign_changes(&azimuthal, true);
+                assert_eq!(polar_nodes, (l as i32 - m.abs()) as usize, "l={l} m={m}");
+                assert_eq!(azimuthal_crossings % 2, 0, "l={l} m={m}");
+                assert_eq!(polar_nodes + azimuthal_crossings / 2, l as usize, "l={l} m={m}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_negative_m_is_signed_conjugate() {
+        let directions = [(0.3_f32, 0.2_f32), (1.1, 2.5), (PI / 2.0, -0.7), (2.6, 4.0)];
+        for l in 0..=3u32 {
+            for m in 1..=(l as i32) {
+                let sign = if m % 2 == 0 { 1.0 } else { -1.0 };
+                for &(theta, phi) in &directions {
+                    let (re_pos, im_pos) = spherical_harmonic(theta, phi, l, m);
+                    let (re_neg, im_neg) = spherical_harmonic(theta, phi, l, -m);
+                    assert!((re_neg - sign * re_pos).abs() < 1e-5, "l={l} m={m} re");
+                    assert!((im_neg + sign * im_pos).abs() < 1e-5, "l={l} m={m} im");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_condon_shortley_phase_values() {
+        let theta = 0.8_f32;
+        let expected = (3.0 / (8.0 * PI)).sqrt() * theta.sin();
+        let (re, im) = spherical_harmonic(theta, 0.0, 1, 1);
+        assert!((re + expected).abs() < 1e-5, "Y_11 should be -sqrt(3/8pi) sin(theta)");
+        assert!(im.abs() < 1e-6);
+        let (re, im) = spherical_harmonic(theta, 0.0, 1, -1);
+        assert!((re - expected).abs() < 1e-5, "Y_1-1 should be +sqrt(3/8pi) sin(theta)");
+        assert!(im.abs() < 1e-6);
+
+        let px = real_spherical_harmonic(theta, 0.0, 1, 1);
+        assert!((px - (3.0 / (4.0 * PI)).sqrt() * theta.sin()).abs() < 1e-5);
+    }
 }